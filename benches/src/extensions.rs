@@ -0,0 +1,49 @@
+#![feature(test)]
+
+extern crate test;
+
+use http::Extensions;
+use test::Bencher;
+
+#[derive(Clone)]
+struct Value<const N: usize>(#[allow(dead_code)] [u64; 4]);
+
+fn ten_values() -> Extensions {
+    let mut ext = Extensions::new();
+    ext.insert(Value::<0>([0; 4]));
+    ext.insert(Value::<1>([1; 4]));
+    ext.insert(Value::<2>([2; 4]));
+    ext.insert(Value::<3>([3; 4]));
+    ext.insert(Value::<4>([4; 4]));
+    ext.insert(Value::<5>([5; 4]));
+    ext.insert(Value::<6>([6; 4]));
+    ext.insert(Value::<7>([7; 4]));
+    ext.insert(String::from("eight"));
+    ext.insert(vec![9u8; 16]);
+    ext
+}
+
+#[bench]
+fn extensions_clone_10(b: &mut Bencher) {
+    let ext = ten_values();
+    b.iter(|| test::black_box(ext.clone()));
+}
+
+#[bench]
+fn extensions_clone_10_and_read(b: &mut Bencher) {
+    let ext = ten_values();
+    b.iter(|| {
+        let copy = ext.clone();
+        test::black_box(copy.get::<String>());
+    });
+}
+
+#[bench]
+fn extensions_clone_10_and_write(b: &mut Bencher) {
+    let ext = ten_values();
+    b.iter(|| {
+        let mut copy = ext.clone();
+        copy.insert(10u32);
+        test::black_box(copy);
+    });
+}
@@ -269,6 +269,14 @@ fn header_name_custom(b: &mut Bencher) {
     });
 }
 
+#[bench]
+fn header_name_custom_long(b: &mut Bencher) {
+    let name = b"x-amzn-request-context-forwarded-for-original-client-address-and-trace-id";
+    b.iter(|| {
+        HeaderName::from_bytes(&name[..]).unwrap();
+    });
+}
+
 #[bench]
 fn header_name_bad(b: &mut Bencher) {
     let name = b"bad header name";
@@ -7,6 +7,7 @@ use http::HeaderValue;
 use test::Bencher;
 
 static SHORT: &'static [u8] = b"localhost";
+const COOKIE_LEN: usize = 4096;
 static LONG: &'static [u8] = b"Mozilla/5.0 (X11; CrOS x86_64 9592.71.0) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/60.0.3112.80 Safari/537.36";
 
 #[bench]
@@ -44,3 +45,27 @@ fn from_shared_unchecked_long(b: &mut Bencher) {
         HeaderValue::from_maybe_shared_unchecked(bytes.clone());
     });
 }
+
+fn cookie() -> Bytes {
+    let pair = b"session_token=AbCdEf0123456789-_; ";
+    pair.iter().copied().cycle().take(COOKIE_LEN).collect::<Vec<u8>>().into()
+}
+
+// Run with `--features simd` to compare against the word-at-a-time checks.
+#[bench]
+fn from_shared_cookie(b: &mut Bencher) {
+    b.bytes = COOKIE_LEN as u64;
+    let bytes = cookie();
+    b.iter(|| {
+        HeaderValue::from_maybe_shared(bytes.clone()).unwrap();
+    });
+}
+
+#[bench]
+fn to_str_cookie(b: &mut Bencher) {
+    b.bytes = COOKIE_LEN as u64;
+    let val = HeaderValue::from_maybe_shared(cookie()).unwrap();
+    b.iter(|| {
+        test::black_box(&val).to_str().unwrap();
+    });
+}
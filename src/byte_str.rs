@@ -60,12 +60,6 @@ impl ByteStr {
         // Invariant: assumed by the safety requirements of this function.
         Self { bytes }
     }
-
-    pub(crate) fn from_utf8(bytes: Bytes) -> Result<Self, std::str::Utf8Error> {
-        str::from_utf8(&bytes)?;
-        // Invariant: just checked is utf8
-        Ok(Self { bytes })
-    }
 }
 
 impl Default for ByteStr {
@@ -0,0 +1,87 @@
+//! Information about the connection a message was received on.
+//!
+//! This module contains [`ConnectionInfo`], a common type for servers to
+//! store details about the underlying connection in a request's
+//! [`Extensions`](crate::Extensions). Middleware can then read it without
+//! depending on a particular server implementation.
+//!
+//! # Examples
+//!
+//! ```
+//! use http::Request;
+//! use http::connection::ConnectionInfo;
+//!
+//! let peer = "192.0.2.1:51000".parse().unwrap();
+//! let local = "198.51.100.7:443".parse().unwrap();
+//!
+//! let mut request = Request::new(());
+//! request.insert_connection_info(ConnectionInfo::new(peer, local).with_alpn_protocol("h2"));
+//!
+//! let info = request.connection_info().unwrap();
+//! assert_eq!(info.peer_addr(), peer);
+//! assert_eq!(info.alpn_protocol(), Some("h2"));
+//! ```
+
+use std::net::SocketAddr;
+
+/// Details about the connection a request was received on.
+///
+/// This type only describes a connection, it does not perform any I/O. New
+/// details may be added in the future, so it can only be created through
+/// [`ConnectionInfo::new`] and the `with_*` methods.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ConnectionInfo {
+    peer_addr: SocketAddr,
+    local_addr: SocketAddr,
+    alpn_protocol: Option<String>,
+}
+
+impl ConnectionInfo {
+    /// Creates a new `ConnectionInfo` from the remote and local addresses of
+    /// a connection.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::connection::ConnectionInfo;
+    /// let info = ConnectionInfo::new(
+    ///     "192.0.2.1:51000".parse().unwrap(),
+    ///     "198.51.100.7:80".parse().unwrap(),
+    /// );
+    /// assert_eq!(info.local_addr().port(), 80);
+    /// assert!(info.alpn_protocol().is_none());
+    /// ```
+    #[must_use]
+    pub const fn new(peer_addr: SocketAddr, local_addr: SocketAddr) -> Self {
+        Self {
+            peer_addr,
+            local_addr,
+            alpn_protocol: None,
+        }
+    }
+
+    /// Sets the protocol negotiated with ALPN, such as `h2` or `http/1.1`.
+    #[must_use]
+    pub fn with_alpn_protocol<T: Into<String>>(mut self, protocol: T) -> Self {
+        self.alpn_protocol = Some(protocol.into());
+        self
+    }
+
+    /// Returns the address of the remote end of the connection.
+    #[must_use]
+    pub const fn peer_addr(&self) -> SocketAddr {
+        self.peer_addr
+    }
+
+    /// Returns the local address the connection was accepted on.
+    #[must_use]
+    pub const fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+
+    /// Returns the protocol negotiated with ALPN, if any.
+    #[must_use]
+    pub fn alpn_protocol(&self) -> Option<&str> {
+        self.alpn_protocol.as_deref()
+    }
+}
@@ -1,12 +1,20 @@
+#[cfg(feature = "backtrace")]
+use std::backtrace::{Backtrace, BacktraceStatus};
 use std::error;
 use std::fmt;
+#[cfg(feature = "debug-location")]
+use std::panic::Location;
 use std::result;
+#[cfg(feature = "backtrace")]
+use std::sync::Arc;
 
 use crate::header;
-use crate::header::MaxSizeReached;
+use crate::header::{ConnectionSpecificHeader, MaxSizeReached};
 use crate::method;
+use crate::response;
 use crate::status;
 use crate::uri;
+use crate::version;
 
 /// A generic "error" for HTTP connections
 ///
@@ -14,21 +22,92 @@ use crate::uri;
 /// functions in this crate, but all other errors can be converted to this
 /// error. Consumers of this crate can typically consume and work with this form
 /// of error for conversions with the `?` operator.
+#[derive(Clone)]
 pub struct Error {
     inner: ErrorKind,
+    #[cfg(feature = "backtrace")]
+    // Only allocated when a backtrace was actually captured.
+    backtrace: Option<Arc<Backtrace>>,
+    #[cfg(feature = "debug-location")]
+    location: Option<&'static Location<'static>>,
 }
 
 /// A `Result` typedef to use with the `http::Error` type
 pub type Result<T> = result::Result<T, Error>;
 
-enum ErrorKind {
-    StatusCode(status::InvalidStatusCode),
-    Method(method::InvalidMethod),
-    Uri(uri::InvalidUri),
-    UriParts(uri::InvalidUriParts),
-    HeaderName(header::InvalidHeaderName),
-    HeaderValue(header::InvalidHeaderValue),
+/// The kind of an [`Error`], holding the error it was converted from.
+///
+/// This is returned by [`Error::kind`] and [`Error::into_kind`]. New kinds
+/// may be added in the future, so matching on it requires a wildcard arm.
+///
+/// # Examples
+///
+/// ```
+/// # use http::{Error, ErrorKind, Uri};
+/// let err: Error = "\n".parse::<Uri>().unwrap_err().into();
+///
+/// match err.kind() {
+///     ErrorKind::InvalidUri(_) | ErrorKind::InvalidUriParts(_) => {}
+///     _ => panic!("unexpected error: {err}"),
+/// }
+/// ```
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// An invalid status code.
+    InvalidStatusCode(status::InvalidStatusCode),
+    /// An invalid reason phrase.
+    InvalidReasonPhrase(response::InvalidReasonPhrase),
+    /// An invalid method.
+    InvalidMethod(method::InvalidMethod),
+    /// An invalid URI.
+    InvalidUri(uri::InvalidUri),
+    /// URI parts that don't form a valid URI.
+    InvalidUriParts(uri::InvalidUriParts),
+    /// An invalid header name.
+    InvalidHeaderName(header::InvalidHeaderName),
+    /// An invalid header value.
+    InvalidHeaderValue(header::InvalidHeaderValue),
+    /// A header map reached its maximum size.
     MaxSizeReached(MaxSizeReached),
+    /// A connection-specific header in an HTTP/2 or later message.
+    ConnectionSpecificHeader(ConnectionSpecificHeader),
+    /// An invalid HTTP version.
+    InvalidVersion(version::InvalidVersion),
+    /// A status code converted into an error.
+    #[cfg(feature = "error")]
+    Status(status::StatusCode),
+    /// Every error collected by a request or response builder.
+    Builder(BuilderErrors),
+}
+
+/// The builder component that failed to be set.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum BuilderField {
+    /// The request method.
+    Method,
+    /// The request URI.
+    Uri,
+    /// The response status.
+    Status,
+    /// The response reason phrase.
+    Reason,
+    /// A header name or value.
+    Header,
+    /// A trailer name or value.
+    Trailer,
+}
+
+/// A collection of every error encountered by a request or response builder.
+///
+/// This is only produced by builders with error collection enabled, see
+/// `request::Builder::collect_errors` and `response::Builder::collect_errors`.
+/// The aggregate is returned wrapped in an `Error`, and can be recovered via
+/// `Error::get_ref` and `downcast_ref`.
+#[derive(Clone)]
+pub struct BuilderErrors {
+    errors: Vec<(BuilderField, Error)>,
 }
 
 impl fmt::Debug for Error {
@@ -40,13 +119,59 @@ impl fmt::Debug for Error {
     }
 }
 
+/// Describes what failed, without repeating the wrapped error, which is
+/// available from `source`.
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        fmt::Display::fmt(self.get_ref(), f)
+        f.write_str(match self.inner {
+            ErrorKind::InvalidStatusCode(_) => "failed to convert status code",
+            ErrorKind::InvalidReasonPhrase(_) => "failed to convert reason phrase",
+            ErrorKind::InvalidMethod(_) => "failed to convert method",
+            ErrorKind::InvalidUri(_) => "failed to convert URI",
+            ErrorKind::InvalidUriParts(_) => "failed to convert URI parts",
+            ErrorKind::InvalidHeaderName(_) => "failed to convert header name",
+            ErrorKind::InvalidHeaderValue(_) => "failed to convert header value",
+            ErrorKind::MaxSizeReached(_) => "header map is full",
+            ErrorKind::ConnectionSpecificHeader(_) => "header not allowed in HTTP/2",
+            ErrorKind::InvalidVersion(_) => "failed to convert version",
+            #[cfg(feature = "error")]
+            ErrorKind::Status(_) => "HTTP error status",
+            ErrorKind::Builder(_) => "failed to build request or response",
+        })?;
+        #[cfg(feature = "debug-location")]
+        if let Some(location) = self.location {
+            write!(f, " (at {location})")?;
+        }
+        Ok(())
     }
 }
 
 impl Error {
+    // Capturing a backtrace isn't const.
+    #[cfg_attr(not(feature = "backtrace"), allow(clippy::missing_const_for_fn))]
+    fn new(inner: ErrorKind) -> Self {
+        Self {
+            inner,
+            #[cfg(feature = "backtrace")]
+            backtrace: {
+                let backtrace = Backtrace::capture();
+                match backtrace.status() {
+                    BacktraceStatus::Captured => Some(Arc::new(backtrace)),
+                    _ => None,
+                }
+            },
+            #[cfg(feature = "debug-location")]
+            location: None,
+        }
+    }
+
+    /// Records the builder call site that produced this error.
+    #[cfg(feature = "debug-location")]
+    pub(crate) const fn with_location(mut self, location: &'static Location<'static>) -> Self {
+        self.location = Some(location);
+        self
+    }
+
     /// Return true if the underlying error has the same type as T.
     #[must_use]
     pub fn is<T: error::Error + 'static>(&self) -> bool {
@@ -56,83 +181,279 @@ impl Error {
     /// Return a reference to the lower level, inner error.
     #[must_use]
     pub fn get_ref(&self) -> &(dyn error::Error + 'static) {
-        use self::ErrorKind::{
-            HeaderName, HeaderValue, MaxSizeReached, Method, StatusCode, Uri, UriParts,
-        };
+        match self.inner {
+            ErrorKind::InvalidStatusCode(ref e) => e,
+            ErrorKind::InvalidReasonPhrase(ref e) => e,
+            ErrorKind::InvalidMethod(ref e) => e,
+            ErrorKind::InvalidUri(ref e) => e,
+            ErrorKind::InvalidUriParts(ref e) => e,
+            ErrorKind::InvalidHeaderName(ref e) => e,
+            ErrorKind::InvalidHeaderValue(ref e) => e,
+            ErrorKind::MaxSizeReached(ref e) => e,
+            ErrorKind::ConnectionSpecificHeader(ref e) => e,
+            ErrorKind::InvalidVersion(ref e) => e,
+            #[cfg(feature = "error")]
+            ErrorKind::Status(ref e) => e,
+            ErrorKind::Builder(ref e) => e,
+        }
+    }
+
+    /// Returns the backtrace captured when this error was created, if any.
+    ///
+    /// Backtraces are only captured if enabled through the `RUST_BACKTRACE`
+    /// or `RUST_LIB_BACKTRACE` environment variables, see
+    /// [`Backtrace::capture`].
+    #[cfg(feature = "backtrace")]
+    #[must_use]
+    pub fn backtrace(&self) -> Option<&Backtrace> {
+        self.backtrace.as_deref()
+    }
+
+    /// Returns the source location of the builder call that produced this
+    /// error, if known.
+    ///
+    /// Locations are recorded for errors raised by the setters of
+    /// `request::Builder` and `response::Builder`, pointing at the offending
+    /// call, and are also appended to the `Display` output. Errors converted
+    /// from other sources, and the aggregate returned in `collect_errors`
+    /// mode, have no location of their own.
+    #[cfg(feature = "debug-location")]
+    #[must_use]
+    pub const fn location(&self) -> Option<&'static Location<'static>> {
+        self.location
+    }
+
+    /// Returns the kind of this error, to match on what went wrong.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::{ErrorKind, HeaderValue};
+    /// let err: http::Error = HeaderValue::from_bytes(b"\n").unwrap_err().into();
+    /// assert!(matches!(err.kind(), ErrorKind::InvalidHeaderValue(_)));
+    /// ```
+    #[must_use]
+    pub const fn kind(&self) -> &ErrorKind {
+        &self.inner
+    }
 
+    /// Consumes this error, returning its kind and the error it holds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::{ErrorKind, Method};
+    /// let err: http::Error = Method::from_bytes(b"").unwrap_err().into();
+    /// let ErrorKind::InvalidMethod(err) = err.into_kind() else {
+    ///     panic!("not a method error");
+    /// };
+    /// assert_eq!(err.to_string(), "invalid HTTP method");
+    /// ```
+    #[must_use]
+    pub fn into_kind(self) -> ErrorKind {
+        self.inner
+    }
+
+    /// Returns true if this error was caused by an invalid URI, that is
+    /// if it wraps a [`uri::InvalidUri`].
+    #[must_use]
+    pub const fn is_invalid_uri(&self) -> bool {
+        matches!(self.inner, ErrorKind::InvalidUri(_))
+    }
+
+    /// Returns true if this error was caused by invalid URI parts, that is
+    /// if it wraps a [`uri::InvalidUriParts`].
+    #[must_use]
+    pub const fn is_invalid_uri_parts(&self) -> bool {
+        matches!(self.inner, ErrorKind::InvalidUriParts(_))
+    }
+
+    /// Returns true if this error was caused by an invalid header name, that is
+    /// if it wraps a [`header::InvalidHeaderName`].
+    #[must_use]
+    pub const fn is_invalid_header_name(&self) -> bool {
+        matches!(self.inner, ErrorKind::InvalidHeaderName(_))
+    }
+
+    /// Returns true if this error was caused by an invalid header value, that
+    /// is if it wraps a [`header::InvalidHeaderValue`].
+    #[must_use]
+    pub const fn is_invalid_header_value(&self) -> bool {
+        matches!(self.inner, ErrorKind::InvalidHeaderValue(_))
+    }
+
+    /// Returns true if this error was caused by an invalid method, that is
+    /// if it wraps a [`method::InvalidMethod`].
+    #[must_use]
+    pub const fn is_invalid_method(&self) -> bool {
+        matches!(self.inner, ErrorKind::InvalidMethod(_))
+    }
+
+    /// Returns true if this error was caused by an invalid status code, that is
+    /// if it wraps a [`status::InvalidStatusCode`].
+    #[must_use]
+    pub const fn is_invalid_status_code(&self) -> bool {
+        matches!(self.inner, ErrorKind::InvalidStatusCode(_))
+    }
+
+    /// Returns the status code this error was created from, if it was
+    /// converted from a `StatusCode`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::{Error, StatusCode};
+    /// let err = Error::from(StatusCode::NOT_FOUND);
+    /// assert_eq!(err.status_code(), Some(StatusCode::NOT_FOUND));
+    ///
+    /// let err = Error::from(StatusCode::from_u16(1000).unwrap_err());
+    /// assert_eq!(err.status_code(), None);
+    /// ```
+    #[cfg(feature = "error")]
+    #[must_use]
+    pub const fn status_code(&self) -> Option<status::StatusCode> {
         match self.inner {
-            StatusCode(ref e) => e,
-            Method(ref e) => e,
-            Uri(ref e) => e,
-            UriParts(ref e) => e,
-            HeaderName(ref e) => e,
-            HeaderValue(ref e) => e,
-            MaxSizeReached(ref e) => e,
+            ErrorKind::Status(status) => Some(status),
+            _ => None,
         }
     }
 }
 
+impl BuilderErrors {
+    pub(crate) const fn new() -> Self {
+        Self { errors: Vec::new() }
+    }
+
+    pub(crate) fn push(&mut self, field: BuilderField, err: Error) {
+        self.errors.push((field, err));
+    }
+
+    /// Returns the number of errors collected.
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.errors.len()
+    }
+
+    /// Returns true if no errors were collected.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// Returns an iterator over the collected errors, in the order the failing
+    /// builder methods were called, each tagged with the failing component.
+    pub fn iter(&self) -> impl Iterator<Item = (BuilderField, &Error)> {
+        self.errors.iter().map(|(field, err)| (*field, err))
+    }
+}
+
+impl fmt::Debug for BuilderErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.errors.iter()).finish()
+    }
+}
+
+impl fmt::Display for BuilderErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, (field, err)) in self.errors.iter().enumerate() {
+            if i > 0 {
+                f.write_str("; ")?;
+            }
+            write!(f, "{field:?}: {err}")?;
+        }
+        Ok(())
+    }
+}
+
+impl error::Error for BuilderErrors {}
+
+impl From<BuilderErrors> for Error {
+    fn from(err: BuilderErrors) -> Self {
+        Self::new(ErrorKind::Builder(err))
+    }
+}
+
+/// The source of an `Error` is the component error it wraps, the same as
+/// [`Error::get_ref`]. The `Display` output of the `Error` only says what
+/// failed, so that error reporters walking the chain don't print the same
+/// message twice.
 impl error::Error for Error {
-    // Return any available cause from the inner error. Note the inner error is
-    // not itself the cause.
     fn source(&self) -> Option<&(dyn error::Error + 'static)> {
-        self.get_ref().source()
+        Some(self.get_ref())
+    }
+}
+
+impl From<ConnectionSpecificHeader> for Error {
+    fn from(err: ConnectionSpecificHeader) -> Self {
+        Self::new(ErrorKind::ConnectionSpecificHeader(err))
     }
 }
 
 impl From<MaxSizeReached> for Error {
     fn from(err: MaxSizeReached) -> Self {
-        Self {
-            inner: ErrorKind::MaxSizeReached(err),
-        }
+        Self::new(ErrorKind::MaxSizeReached(err))
+    }
+}
+
+/// Converts a status code into an `Error`, so that a handler returning
+/// `http::Result` can bail out with a status, as in
+/// `return Err(StatusCode::NOT_FOUND.into())`.
+///
+/// The status code can be recovered with [`Error::status_code`] to build the
+/// error response. Any status code is accepted, including success codes;
+/// deciding what they mean is up to the caller.
+#[cfg(feature = "error")]
+impl From<status::StatusCode> for Error {
+    fn from(status: status::StatusCode) -> Self {
+        Self::new(ErrorKind::Status(status))
     }
 }
 
 impl From<status::InvalidStatusCode> for Error {
     fn from(err: status::InvalidStatusCode) -> Self {
-        Self {
-            inner: ErrorKind::StatusCode(err),
-        }
+        Self::new(ErrorKind::InvalidStatusCode(err))
+    }
+}
+
+impl From<response::InvalidReasonPhrase> for Error {
+    fn from(err: response::InvalidReasonPhrase) -> Self {
+        Self::new(ErrorKind::InvalidReasonPhrase(err))
     }
 }
 
 impl From<method::InvalidMethod> for Error {
     fn from(err: method::InvalidMethod) -> Self {
-        Self {
-            inner: ErrorKind::Method(err),
-        }
+        Self::new(ErrorKind::InvalidMethod(err))
+    }
+}
+
+impl From<version::InvalidVersion> for Error {
+    fn from(err: version::InvalidVersion) -> Self {
+        Self::new(ErrorKind::InvalidVersion(err))
     }
 }
 
 impl From<uri::InvalidUri> for Error {
     fn from(err: uri::InvalidUri) -> Self {
-        Self {
-            inner: ErrorKind::Uri(err),
-        }
+        Self::new(ErrorKind::InvalidUri(err))
     }
 }
 
 impl From<uri::InvalidUriParts> for Error {
     fn from(err: uri::InvalidUriParts) -> Self {
-        Self {
-            inner: ErrorKind::UriParts(err),
-        }
+        Self::new(ErrorKind::InvalidUriParts(err))
     }
 }
 
 impl From<header::InvalidHeaderName> for Error {
     fn from(err: header::InvalidHeaderName) -> Self {
-        Self {
-            inner: ErrorKind::HeaderName(err),
-        }
+        Self::new(ErrorKind::InvalidHeaderName(err))
     }
 }
 
 impl From<header::InvalidHeaderValue> for Error {
     fn from(err: header::InvalidHeaderValue) -> Self {
-        Self {
-            inner: ErrorKind::HeaderValue(err),
-        }
+        Self::new(ErrorKind::InvalidHeaderValue(err))
     }
 }
 
@@ -161,4 +482,190 @@ mod tests {
             panic!("Bad status allowed!");
         }
     }
+
+    #[test]
+    fn kind_predicates() {
+        fn predicates(err: &Error) -> [bool; 6] {
+            [
+                err.is_invalid_uri(),
+                err.is_invalid_uri_parts(),
+                err.is_invalid_header_name(),
+                err.is_invalid_header_value(),
+                err.is_invalid_method(),
+                err.is_invalid_status_code(),
+            ]
+        }
+
+        let errors: [Error; 6] = [
+            "\n".parse::<uri::Uri>().unwrap_err().into(),
+            {
+                let mut parts = uri::Parts::new();
+                parts.scheme = Some(uri::Scheme::HTTP);
+                uri::Uri::from_parts(parts).unwrap_err().into()
+            },
+            header::HeaderName::from_bytes(b"\n").unwrap_err().into(),
+            header::HeaderValue::from_bytes(b"\n").unwrap_err().into(),
+            method::Method::from_bytes(b"").unwrap_err().into(),
+            status::StatusCode::from_u16(6666).unwrap_err().into(),
+        ];
+
+        for (i, err) in errors.iter().enumerate() {
+            let predicates = predicates(err);
+            for (j, predicate) in predicates.into_iter().enumerate() {
+                assert_eq!(predicate, i == j, "{err:?}: predicate {j}");
+            }
+        }
+
+        let err: Error = BuilderErrors::new().into();
+        assert_eq!(predicates(&err), [false; 6]);
+    }
+
+    /// Returns an error of every kind, with a name for the kind.
+    fn every_kind() -> [(Error, &'static str); 11] {
+        let mut headers = header::HeaderMap::new();
+        headers.insert(
+            header::CONNECTION,
+            header::HeaderValue::from_static("close"),
+        );
+
+        [
+            (
+                status::StatusCode::from_u16(6666).unwrap_err().into(),
+                "status code",
+            ),
+            (
+                response::ReasonPhrase::try_from("\n").unwrap_err().into(),
+                "reason phrase",
+            ),
+            (
+                method::Method::from_bytes(b"").unwrap_err().into(),
+                "method",
+            ),
+            ("\n".parse::<uri::Uri>().unwrap_err().into(), "uri"),
+            (
+                {
+                    let mut parts = uri::Parts::new();
+                    parts.scheme = Some(uri::Scheme::HTTP);
+                    uri::Uri::from_parts(parts).unwrap_err().into()
+                },
+                "uri parts",
+            ),
+            (
+                header::HeaderName::from_bytes(b"\n").unwrap_err().into(),
+                "header name",
+            ),
+            (
+                header::HeaderValue::from_bytes(b"\n").unwrap_err().into(),
+                "header value",
+            ),
+            (
+                header::HeaderMap::<()>::try_with_capacity(1 << 16)
+                    .unwrap_err()
+                    .into(),
+                "max size reached",
+            ),
+            (
+                headers.check_connection_specific().unwrap_err().into(),
+                "connection-specific header",
+            ),
+            (
+                "HTTP/1.x".parse::<version::Version>().unwrap_err().into(),
+                "version",
+            ),
+            (BuilderErrors::new().into(), "builder"),
+        ]
+    }
+
+    #[test]
+    fn kind_matches_every_source() {
+        fn name(err: &Error) -> &'static str {
+            match err.kind() {
+                ErrorKind::InvalidStatusCode(_) => "status code",
+                ErrorKind::InvalidReasonPhrase(_) => "reason phrase",
+                ErrorKind::InvalidMethod(_) => "method",
+                ErrorKind::InvalidUri(_) => "uri",
+                ErrorKind::InvalidUriParts(_) => "uri parts",
+                ErrorKind::InvalidHeaderName(_) => "header name",
+                ErrorKind::InvalidHeaderValue(_) => "header value",
+                ErrorKind::MaxSizeReached(_) => "max size reached",
+                ErrorKind::ConnectionSpecificHeader(_) => "connection-specific header",
+                ErrorKind::InvalidVersion(_) => "version",
+                ErrorKind::Builder(_) => "builder",
+                #[allow(unreachable_patterns)]
+                _ => "other",
+            }
+        }
+
+        let errors = every_kind();
+
+        for (err, expected) in errors {
+            assert_eq!(name(&err), expected, "{err:?}");
+            assert_ne!(err.get_ref().to_string(), err.to_string());
+
+            let kind = err.into_kind();
+            assert!(!format!("{kind:?}").is_empty());
+        }
+    }
+
+    #[test]
+    fn source_is_the_wrapped_error() {
+        use std::error::Error as _;
+
+        for (err, _) in every_kind() {
+            let source = err.source().unwrap();
+            assert!(std::ptr::addr_eq(source, err.get_ref()));
+            assert_ne!(source.to_string(), err.to_string());
+        }
+    }
+
+    #[cfg(feature = "backtrace")]
+    #[test]
+    fn backtrace_follows_the_environment() {
+        let err: Error = method::Method::from_bytes(b"").unwrap_err().into();
+        let captured = Backtrace::capture().status() == BacktraceStatus::Captured;
+        let clone = err.clone();
+        assert_eq!(clone.backtrace().is_some(), captured);
+        assert_eq!(err.backtrace().is_some(), captured);
+    }
+
+    #[test]
+    fn errors_are_cloneable() {
+        for (err, _) in every_kind() {
+            let clone = err.clone();
+            assert_eq!(clone.to_string(), err.to_string());
+            assert_eq!(format!("{clone:?}"), format!("{err:?}"));
+            assert_eq!(
+                format!("{:?}", clone.kind().clone()),
+                format!("{:?}", err.into_kind())
+            );
+        }
+
+        let invalid_uri = "\n".parse::<uri::Uri>().unwrap_err();
+        assert_eq!(invalid_uri.clone(), invalid_uri);
+        assert_ne!(invalid_uri, "".parse::<uri::Uri>().unwrap_err());
+    }
+
+    #[cfg(feature = "error")]
+    #[test]
+    fn status_code_converts_into_error() {
+        fn handler(found: bool) -> Result<&'static str> {
+            if !found {
+                return Err(status::StatusCode::NOT_FOUND.into());
+            }
+            Ok("found")
+        }
+
+        let err = handler(false).unwrap_err();
+        assert_eq!(err.status_code(), Some(status::StatusCode::NOT_FOUND));
+        assert!(err.is::<status::StatusCode>());
+        assert_eq!(err.to_string(), "HTTP error status");
+        assert_eq!(
+            std::error::Error::source(&err).unwrap().to_string(),
+            "404 Not Found"
+        );
+        assert_eq!(handler(true).unwrap(), "found");
+
+        let err: Error = header::HeaderValue::from_bytes(b"\n").unwrap_err().into();
+        assert_eq!(err.status_code(), None);
+    }
 }
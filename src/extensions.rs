@@ -1,7 +1,16 @@
+//! Typed protocol extensions
+//!
+//! This module contains [`Extensions`], a map keyed by type that `Request`
+//! and `Response` use to carry extra data, and the [`Entry`] API for
+//! in-place manipulation of a single extension.
+
 use std::any::{Any, TypeId};
 use std::collections::HashMap;
+use std::collections::hash_map;
 use std::fmt;
 use std::hash::{BuildHasherDefault, Hasher};
+use std::marker::PhantomData;
+use std::sync::Arc;
 
 type AnyMap = HashMap<TypeId, Box<dyn AnyClone + Send + Sync>, BuildHasherDefault<IdHasher>>;
 
@@ -31,21 +40,55 @@ impl Hasher for IdHasher {
 ///
 /// `Extensions` can be used by `Request` and `Response` to store
 /// extra data derived from the underlying protocol.
+///
+/// Cloning `Extensions` is cheap: the clone shares its storage with the
+/// original until either one is modified, at which point every value is
+/// deeply cloned. This is why every extension value must implement `Clone`.
+/// If values that can't be cloned are ever supported, they will be absent
+/// from the clone.
 #[derive(Clone, Default)]
 pub struct Extensions {
     // If extensions are never used, no need to carry around an empty HashMap.
     // That's 3 words. Instead, this is only 1 word.
-    map: Option<Box<AnyMap>>,
+    //
+    // The map is shared between clones, and copied on the first write.
+    map: Option<Arc<AnyMap>>,
 }
 
 impl Extensions {
     /// Create an empty `Extensions`.
+    ///
+    /// This does not allocate. No storage is allocated until the first
+    /// extension is inserted, or capacity is requested with
+    /// [`reserve`](Self::reserve).
     #[inline]
     #[must_use]
     pub const fn new() -> Self {
         Self { map: None }
     }
 
+    /// Create an empty `Extensions` with room for at least `capacity`
+    /// extensions without reallocating.
+    ///
+    /// If `capacity` is 0, this does not allocate.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use http::Extensions;
+    /// let ext = Extensions::with_capacity(8);
+    /// assert!(ext.is_empty());
+    /// assert!(ext.capacity() >= 8);
+    ///
+    /// assert_eq!(Extensions::with_capacity(0).capacity(), 0);
+    /// ```
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> Self {
+        let mut ext = Self::new();
+        ext.reserve(capacity);
+        ext
+    }
+
     /// Insert a type into this `Extensions`.
     ///
     /// If a extension of this type already existed, it will
@@ -61,8 +104,7 @@ impl Extensions {
     /// assert_eq!(ext.insert(9i32), Some(5i32));
     /// ```
     pub fn insert<T: Clone + Send + Sync + 'static>(&mut self, val: T) -> Option<T> {
-        self.map
-            .get_or_insert_with(Box::default)
+        self.map_mut()
             .insert(TypeId::of::<T>(), Box::new(val))
             .and_then(|boxed| boxed.into_any().downcast().ok().map(|boxed| *boxed))
     }
@@ -100,8 +142,7 @@ impl Extensions {
     /// assert_eq!(ext.get::<String>().unwrap(), "Hello World");
     /// ```
     pub fn get_mut<T: Send + Sync + 'static>(&mut self) -> Option<&mut T> {
-        self.map
-            .as_mut()
+        self.existing_map_mut(TypeId::of::<T>())
             .and_then(|map| map.get_mut(&TypeId::of::<T>()))
             .and_then(|boxed| (**boxed).as_any_mut().downcast_mut())
     }
@@ -139,8 +180,7 @@ impl Extensions {
         f: F,
     ) -> &mut T {
         let out = self
-            .map
-            .get_or_insert_with(Box::default)
+            .map_mut()
             .entry(TypeId::of::<T>())
             .or_insert_with(|| Box::new(f()));
         (**out).as_any_mut().downcast_mut().unwrap()
@@ -162,6 +202,52 @@ impl Extensions {
         self.get_or_insert_with(T::default)
     }
 
+    /// Gets the entry for the extension of type `T`, for in-place
+    /// manipulation.
+    ///
+    /// This only looks up the type once, however the entry is then used.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use http::Extensions;
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct Settings {
+    ///     retries: u32,
+    /// }
+    ///
+    /// let mut ext = Extensions::new();
+    ///
+    /// // Only inserts the defaults if no layer configured settings.
+    /// ext.entry().or_insert_with(|| Settings { retries: 3 });
+    /// ext.entry().or_insert_with(|| Settings { retries: 5 });
+    /// assert_eq!(ext.get(), Some(&Settings { retries: 3 }));
+    ///
+    /// ext.entry::<Settings>()
+    ///     .and_modify(|settings| settings.retries += 1)
+    ///     .or_insert(Settings { retries: 0 });
+    /// assert_eq!(ext.get(), Some(&Settings { retries: 4 }));
+    /// ```
+    pub fn entry<T: Clone + Send + Sync + 'static>(&mut self) -> Entry<'_, T> {
+        let id = TypeId::of::<T>();
+
+        // A vacant entry only allocates or copies the map once it's filled.
+        if !self.map.as_ref().is_some_and(|map| map.contains_key(&id)) {
+            return Entry::Vacant(VacantEntry {
+                extensions: self,
+                _marker: PhantomData,
+            });
+        }
+
+        match self.map_mut().entry(id) {
+            hash_map::Entry::Occupied(inner) => Entry::Occupied(OccupiedEntry {
+                inner,
+                _marker: PhantomData,
+            }),
+            hash_map::Entry::Vacant(_) => unreachable!("extension checked to be present"),
+        }
+    }
+
     /// Remove a type from this `Extensions`.
     ///
     /// If a extension of this type existed, it will be returned.
@@ -176,12 +262,34 @@ impl Extensions {
     /// assert!(ext.get::<i32>().is_none());
     /// ```
     pub fn remove<T: Send + Sync + 'static>(&mut self) -> Option<T> {
-        self.map
-            .as_mut()
+        self.existing_map_mut(TypeId::of::<T>())
             .and_then(|map| map.remove(&TypeId::of::<T>()))
             .and_then(|boxed| boxed.into_any().downcast().ok().map(|boxed| *boxed))
     }
 
+    /// Remove the extension with the given `TypeId`, if any.
+    ///
+    /// Returns whether an extension was removed. This is intended for tooling
+    /// that tracks extensions without knowing their types statically; prefer
+    /// [`remove`](Self::remove) otherwise.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use http::Extensions;
+    /// # use std::any::TypeId;
+    /// let mut ext = Extensions::new();
+    /// ext.insert(5i32);
+    ///
+    /// assert!(ext.remove_by_type_id(TypeId::of::<i32>()));
+    /// assert!(!ext.remove_by_type_id(TypeId::of::<i32>()));
+    /// assert!(ext.is_empty());
+    /// ```
+    pub fn remove_by_type_id(&mut self, id: TypeId) -> bool {
+        self.existing_map_mut(id)
+            .is_some_and(|map| map.remove(&id).is_some())
+    }
+
     /// Clear the `Extensions` of all inserted extensions.
     ///
     /// # Example
@@ -197,7 +305,11 @@ impl Extensions {
     #[inline]
     pub fn clear(&mut self) {
         if let Some(ref mut map) = self.map {
-            map.clear();
+            match Arc::get_mut(map) {
+                Some(map) => map.clear(),
+                // Shared with a clone, so there's nothing worth keeping.
+                None => self.map = None,
+            }
         }
     }
 
@@ -235,6 +347,103 @@ impl Extensions {
         self.map.as_ref().map_or(0, |map| map.len())
     }
 
+    /// Returns the number of extensions this `Extensions` can hold without
+    /// reallocating.
+    ///
+    /// The number is a lower bound, and is 0 if no storage has been
+    /// allocated yet.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use http::Extensions;
+    /// let mut ext = Extensions::new();
+    /// assert_eq!(ext.capacity(), 0);
+    /// ext.insert(5i32);
+    /// assert!(ext.capacity() >= 1);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.map.as_ref().map_or(0, |map| map.capacity())
+    }
+
+    /// Reserves capacity for at least `additional` more extensions.
+    ///
+    /// Inserting that many extensions of new types afterwards will not
+    /// reallocate. Reserving 0 does nothing.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new allocation size overflows `usize`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use http::Extensions;
+    /// let mut ext = Extensions::new();
+    /// ext.reserve(2);
+    ///
+    /// let capacity = ext.capacity();
+    /// ext.insert(5i32);
+    /// ext.insert(4u8);
+    /// assert_eq!(ext.capacity(), capacity);
+    /// ```
+    pub fn reserve(&mut self, additional: usize) {
+        if additional > 0 {
+            self.map_mut().reserve(additional);
+        }
+    }
+
+    /// Shrinks the capacity of the `Extensions` as much as possible.
+    ///
+    /// If there are no extensions left, the storage is released entirely.
+    /// Storage that is still shared with a clone is left as is.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use http::Extensions;
+    /// let mut ext = Extensions::with_capacity(32);
+    /// ext.insert(5i32);
+    /// ext.shrink_to_fit();
+    /// assert!(ext.capacity() >= 1);
+    ///
+    /// ext.clear();
+    /// ext.shrink_to_fit();
+    /// assert_eq!(ext.capacity(), 0);
+    /// ```
+    pub fn shrink_to_fit(&mut self) {
+        if self.is_empty() {
+            self.map = None;
+        } else if let Some(map) = self.map.as_mut().and_then(Arc::get_mut) {
+            map.shrink_to_fit();
+        }
+    }
+
+    /// Returns an iterator over the type names of the stored extensions, in
+    /// no particular order.
+    ///
+    /// The names come from [`std::any::type_name`], so they are only meant
+    /// for debugging.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use http::Extensions;
+    /// let mut ext = Extensions::new();
+    /// ext.insert(5i32);
+    ///
+    /// assert_eq!(ext.type_names().collect::<Vec<_>>(), ["i32"]);
+    /// assert_eq!(format!("{ext:?}"), r#"Extensions(["i32"])"#);
+    /// ```
+    pub fn type_names(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.map
+            .iter()
+            .flat_map(|map| map.values())
+            .map(|boxed| (**boxed).type_name())
+    }
+
     /// Extends `self` with another `Extensions`.
     ///
     /// If an instance of a specific type exists in both, the one in `self` is overwritten with the
@@ -261,25 +470,249 @@ impl Extensions {
     pub fn extend(&mut self, other: Self) {
         if let Some(other) = other.map {
             if let Some(map) = &mut self.map {
-                map.extend(*other);
+                Arc::make_mut(map).extend(Arc::unwrap_or_clone(other));
             } else {
                 self.map = Some(other);
             }
         }
     }
+
+    /// Clones every extension of `other` into `self`.
+    ///
+    /// This is the non-destructive form of [`extend`](Self::extend): if an
+    /// instance of a specific type exists in both, the one in `self` is
+    /// overwritten with a clone of the one from `other`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use http::Extensions;
+    /// let mut ext_a = Extensions::new();
+    /// ext_a.insert(8u8);
+    ///
+    /// let mut ext_b = Extensions::new();
+    /// ext_b.insert(4u8);
+    /// ext_b.insert(16u16);
+    ///
+    /// ext_a.merge_cloned(&ext_b);
+    /// assert_eq!(ext_a.get::<u8>(), Some(&4u8));
+    /// assert_eq!(ext_a.get::<u16>(), Some(&16u16));
+    /// assert_eq!(ext_b.len(), 2);
+    /// ```
+    pub fn merge_cloned(&mut self, other: &Self) {
+        if let Some(other) = other.map.as_ref().filter(|other| !other.is_empty()) {
+            if self.is_empty() {
+                self.map = Some(Arc::clone(other));
+            } else {
+                self.map_mut()
+                    .extend(other.iter().map(|(id, value)| (*id, value.clone())));
+            }
+        }
+    }
+
+    /// Returns the map for modification, copying it first if it is shared
+    /// with a clone.
+    fn map_mut(&mut self) -> &mut AnyMap {
+        Arc::make_mut(self.map.get_or_insert_with(Arc::default))
+    }
+
+    /// Like `map_mut`, but returns `None`, without allocating or copying,
+    /// unless an extension with the given `TypeId` is present.
+    fn existing_map_mut(&mut self, id: TypeId) -> Option<&mut AnyMap> {
+        let map = self.map.as_mut()?;
+
+        if map.contains_key(&id) {
+            Some(Arc::make_mut(map))
+        } else {
+            None
+        }
+    }
 }
 
+/// Lists the type names of the stored extensions, in no particular order.
+/// The values themselves are not shown.
 impl fmt::Debug for Extensions {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.debug_struct("Extensions").finish()
+        struct TypeNames<'a>(&'a Extensions);
+
+        impl fmt::Debug for TypeNames<'_> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.debug_list().entries(self.0.type_names()).finish()
+            }
+        }
+
+        f.debug_tuple("Extensions").field(&TypeNames(self)).finish()
+    }
+}
+
+/// A view into the extension of a single type in an `Extensions`.
+///
+/// This is returned by [`Extensions::entry`].
+pub enum Entry<'a, T> {
+    /// An extension of this type is present.
+    Occupied(OccupiedEntry<'a, T>),
+
+    /// No extension of this type is present.
+    Vacant(VacantEntry<'a, T>),
+}
+
+/// A view into an extension that is present in an `Extensions`.
+///
+/// This is part of the [`Entry`] enum.
+pub struct OccupiedEntry<'a, T> {
+    inner: hash_map::OccupiedEntry<'a, TypeId, Box<dyn AnyClone + Send + Sync>>,
+    _marker: PhantomData<fn() -> T>,
+}
+
+/// A view into a missing extension in an `Extensions`.
+///
+/// This is part of the [`Entry`] enum.
+pub struct VacantEntry<'a, T> {
+    extensions: &'a mut Extensions,
+    _marker: PhantomData<fn() -> T>,
+}
+
+// ===== impl Entry =====
+
+impl<'a, T: Clone + Send + Sync + 'static> Entry<'a, T> {
+    /// Ensures the extension is present by inserting `default` if it is
+    /// missing, and returns a mutable reference to it.
+    pub fn or_insert(self, default: T) -> &'a mut T {
+        self.or_insert_with(|| default)
+    }
+
+    /// Ensures the extension is present by inserting the result of `default`
+    /// if it is missing, and returns a mutable reference to it.
+    ///
+    /// `default` is only called if the extension is missing.
+    pub fn or_insert_with<F: FnOnce() -> T>(self, default: F) -> &'a mut T {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(default()),
+        }
+    }
+
+    /// Ensures the extension is present by inserting `T::default()` if it is
+    /// missing, and returns a mutable reference to it.
+    pub fn or_default(self) -> &'a mut T
+    where
+        T: Default,
+    {
+        self.or_insert_with(T::default)
+    }
+
+    /// Calls `f` with the extension if it is present, before any potential
+    /// insertion.
+    #[must_use]
+    pub fn and_modify<F: FnOnce(&mut T)>(mut self, f: F) -> Self {
+        if let Entry::Occupied(entry) = &mut self {
+            f(entry.get_mut());
+        }
+        self
+    }
+}
+
+impl<T> fmt::Debug for Entry<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Entry::Occupied(entry) => f.debug_tuple("Entry").field(entry).finish(),
+            Entry::Vacant(entry) => f.debug_tuple("Entry").field(entry).finish(),
+        }
+    }
+}
+
+// ===== impl OccupiedEntry =====
+
+impl<'a, T: Clone + Send + Sync + 'static> OccupiedEntry<'a, T> {
+    /// Returns a reference to the extension.
+    #[must_use]
+    pub fn get(&self) -> &T {
+        downcast_ref(&**self.inner.get())
+    }
+
+    /// Returns a mutable reference to the extension.
+    pub fn get_mut(&mut self) -> &mut T {
+        downcast_mut(&mut **self.inner.get_mut())
+    }
+
+    /// Converts the entry into a mutable reference to the extension, with
+    /// the lifetime of the `Extensions`.
+    #[must_use]
+    pub fn into_mut(self) -> &'a mut T {
+        downcast_mut(&mut **self.inner.into_mut())
+    }
+
+    /// Replaces the extension, returning the previous value.
+    pub fn insert(&mut self, value: T) -> T {
+        std::mem::replace(self.get_mut(), value)
+    }
+
+    /// Removes the extension from the `Extensions`, returning it.
+    #[must_use]
+    pub fn remove(self) -> T {
+        downcast_owned(self.inner.remove())
+    }
+}
+
+impl<T> fmt::Debug for OccupiedEntry<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OccupiedEntry")
+            .field("type", &std::any::type_name::<T>())
+            .finish()
     }
 }
 
+// ===== impl VacantEntry =====
+
+impl<'a, T: Clone + Send + Sync + 'static> VacantEntry<'a, T> {
+    /// Inserts the extension, returning a mutable reference to it.
+    pub fn insert(self, value: T) -> &'a mut T {
+        let boxed = self
+            .extensions
+            .map_mut()
+            .entry(TypeId::of::<T>())
+            .or_insert_with(|| Box::new(value));
+        downcast_mut(&mut **boxed)
+    }
+}
+
+impl<T> fmt::Debug for VacantEntry<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("VacantEntry")
+            .field("type", &std::any::type_name::<T>())
+            .finish()
+    }
+}
+
+// Entries are keyed by `TypeId::of::<T>()`, so their value is always a `T`.
+
+fn downcast_ref<T: 'static>(boxed: &(dyn AnyClone + Send + Sync)) -> &T {
+    boxed
+        .as_any()
+        .downcast_ref()
+        .expect("extension stored under the wrong TypeId")
+}
+
+fn downcast_mut<T: 'static>(boxed: &mut (dyn AnyClone + Send + Sync)) -> &mut T {
+    boxed
+        .as_any_mut()
+        .downcast_mut()
+        .expect("extension stored under the wrong TypeId")
+}
+
+fn downcast_owned<T: 'static>(boxed: Box<dyn AnyClone + Send + Sync>) -> T {
+    *boxed
+        .into_any()
+        .downcast()
+        .expect("extension stored under the wrong TypeId")
+}
+
 trait AnyClone: Any {
     fn clone_box(&self) -> Box<dyn AnyClone + Send + Sync>;
     fn as_any(&self) -> &dyn Any;
     fn as_any_mut(&mut self) -> &mut dyn Any;
     fn into_any(self: Box<Self>) -> Box<dyn Any>;
+    fn type_name(&self) -> &'static str;
 }
 
 impl<T: Clone + Send + Sync + 'static> AnyClone for T {
@@ -298,6 +731,10 @@ impl<T: Clone + Send + Sync + 'static> AnyClone for T {
     fn into_any(self: Box<Self>) -> Box<dyn Any> {
         self
     }
+
+    fn type_name(&self) -> &'static str {
+        std::any::type_name::<T>()
+    }
 }
 
 impl Clone for Box<dyn AnyClone + Send + Sync> {
@@ -331,3 +768,224 @@ fn test_extensions() {
     assert_eq!(extensions.get::<bool>(), None);
     assert_eq!(extensions.get(), Some(&MyType(10)));
 }
+
+#[test]
+fn test_extensions_len() {
+    let mut extensions = Extensions::new();
+    assert!(extensions.is_empty());
+    assert_eq!(extensions.len(), 0);
+    extensions.clear();
+    assert!(extensions.map.is_none());
+
+    extensions.insert(1u8);
+    extensions.insert(2u16);
+    extensions.insert(3u8);
+    assert_eq!(extensions.len(), 2);
+
+    assert_eq!(extensions.remove::<u8>(), Some(3));
+    assert_eq!(extensions.len(), 1);
+    assert!(!extensions.remove_by_type_id(TypeId::of::<u8>()));
+    assert!(extensions.remove_by_type_id(TypeId::of::<u16>()));
+    assert!(extensions.is_empty());
+
+    extensions.insert(4u32);
+    extensions.insert("four");
+    extensions.clear();
+    assert_eq!(extensions.len(), 0);
+    assert!(extensions.get::<u32>().is_none());
+}
+
+#[test]
+fn test_extensions_merge() {
+    #[derive(Clone, Debug, PartialEq)]
+    struct Scratch(&'static str);
+
+    let mut scratch = Extensions::new();
+    scratch.insert(Scratch("layer"));
+    scratch.insert(2u8);
+
+    let mut request = Extensions::new();
+    request.insert(1u8);
+    request.insert(7u64);
+
+    request.merge_cloned(&scratch);
+    assert_eq!(request.len(), 3);
+    assert_eq!(request.get(), Some(&2u8));
+    assert_eq!(request.get(), Some(&7u64));
+    assert_eq!(request.get(), Some(&Scratch("layer")));
+    assert_eq!(scratch.len(), 2);
+
+    let mut empty = Extensions::new();
+    empty.merge_cloned(&Extensions::new());
+    assert!(empty.map.is_none());
+
+    scratch.insert(3u8);
+    request.extend(scratch);
+    assert_eq!(request.get(), Some(&3u8));
+    assert_eq!(request.len(), 3);
+}
+
+#[test]
+fn test_extensions_entry() {
+    use std::cell::Cell;
+
+    let constructed = Cell::new(0);
+    let make = || {
+        constructed.set(constructed.get() + 1);
+        String::from("default")
+    };
+
+    let mut extensions = Extensions::new();
+    assert_eq!(extensions.entry().or_insert_with(make), "default");
+    assert_eq!(extensions.entry().or_insert_with(make), "default");
+    assert_eq!(constructed.get(), 1);
+
+    extensions
+        .entry::<String>()
+        .and_modify(|s| s.push_str("-modified"))
+        .or_insert_with(make);
+    assert_eq!(extensions.get::<String>().unwrap(), "default-modified");
+    assert_eq!(constructed.get(), 1);
+
+    *extensions.entry::<u32>().or_default() += 2;
+    assert_eq!(extensions.get(), Some(&2u32));
+
+    match extensions.entry::<u32>() {
+        Entry::Occupied(mut entry) => {
+            assert_eq!(*entry.get(), 2);
+            assert_eq!(entry.insert(7), 2);
+            assert_eq!(entry.remove(), 7);
+        }
+        Entry::Vacant(_) => panic!("u32 extension should be present"),
+    }
+    assert!(extensions.get::<u32>().is_none());
+
+    match extensions.entry::<u32>() {
+        Entry::Vacant(entry) => *entry.insert(1) += 1,
+        Entry::Occupied(_) => panic!("u32 extension should be missing"),
+    }
+    assert_eq!(extensions.get(), Some(&2u32));
+    assert_eq!(extensions.len(), 2);
+}
+
+#[test]
+fn test_extensions_type_names() {
+    #[derive(Clone)]
+    struct RequestId(#[allow(dead_code)] u64);
+
+    let mut extensions = Extensions::new();
+    assert_eq!(extensions.type_names().count(), 0);
+    assert_eq!(format!("{extensions:?}"), "Extensions([])");
+
+    extensions.insert(RequestId(1));
+    extensions.insert(5u8);
+
+    let mut names: Vec<_> = extensions.type_names().collect();
+    names.sort_unstable();
+    assert_eq!(
+        names,
+        [
+            "http::extensions::test_extensions_type_names::RequestId",
+            "u8"
+        ]
+    );
+    assert!(format!("{extensions:?}").contains("RequestId"));
+
+    extensions.remove::<RequestId>();
+    assert_eq!(extensions.type_names().collect::<Vec<_>>(), ["u8"]);
+    assert_eq!(format!("{extensions:?}"), r#"Extensions(["u8"])"#);
+}
+
+#[test]
+fn test_extensions_copy_on_write() {
+    let mut original = Extensions::new();
+    for i in 0..10u8 {
+        original.insert(vec![i]);
+        original.insert(i32::from(i));
+    }
+    original.insert(String::from("shared"));
+
+    let mut copy = original.clone();
+    assert!(Arc::ptr_eq(
+        original.map.as_ref().unwrap(),
+        copy.map.as_ref().unwrap()
+    ));
+    assert_eq!(original.get::<String>().unwrap(), "shared");
+    assert_eq!(copy.get::<String>().unwrap(), "shared");
+
+    // Misses don't copy the storage.
+    assert!(copy.get_mut::<u64>().is_none());
+    assert!(copy.remove::<u64>().is_none());
+    assert!(matches!(copy.entry::<u64>(), Entry::Vacant(_)));
+    assert!(Arc::ptr_eq(
+        original.map.as_ref().unwrap(),
+        copy.map.as_ref().unwrap()
+    ));
+
+    copy.get_mut::<String>().unwrap().push_str("-copy");
+    assert!(!Arc::ptr_eq(
+        original.map.as_ref().unwrap(),
+        copy.map.as_ref().unwrap()
+    ));
+    assert_eq!(original.get::<String>().unwrap(), "shared");
+    assert_eq!(copy.get::<String>().unwrap(), "shared-copy");
+
+    let mut third = original.clone();
+    third.clear();
+    assert!(third.is_empty());
+    assert_eq!(original.len(), 3);
+}
+
+#[test]
+fn test_extensions_capacity() {
+    // Nothing is allocated until it's needed.
+    let mut ext = Extensions::new();
+    assert!(ext.map.is_none());
+    assert!(ext.get::<i32>().is_none());
+    assert!(ext.get_mut::<i32>().is_none());
+    assert!(ext.remove::<i32>().is_none());
+    assert!(matches!(ext.entry::<i32>(), Entry::Vacant(_)));
+    ext.reserve(0);
+    ext.clear();
+    ext.shrink_to_fit();
+    assert!(ext.map.is_none());
+    assert!(Extensions::with_capacity(0).map.is_none());
+    assert!(Extensions::default().map.is_none());
+
+    // Reserved capacity is used without reallocating.
+    let mut ext = Extensions::with_capacity(8);
+    let capacity = ext.capacity();
+    assert!(capacity >= 8);
+    ext.insert(1u8);
+    ext.insert(2u16);
+    ext.insert(3u32);
+    ext.insert(4u64);
+    ext.insert(5i8);
+    ext.insert(6i16);
+    ext.insert(7i32);
+    ext.insert(8i64);
+    assert_eq!(ext.len(), 8);
+    assert_eq!(ext.capacity(), capacity);
+
+    ext.reserve(16);
+    assert!(ext.capacity() >= 24);
+
+    ext.shrink_to_fit();
+    assert!(ext.capacity() >= 8);
+    assert!(ext.capacity() < 24);
+
+    // Shrinking doesn't touch storage shared with a clone.
+    let mut ext = Extensions::with_capacity(32);
+    ext.insert(1u8);
+    let copy = ext.clone();
+    ext.shrink_to_fit();
+    assert!(Arc::ptr_eq(
+        ext.map.as_ref().unwrap(),
+        copy.map.as_ref().unwrap()
+    ));
+
+    ext.clear();
+    ext.shrink_to_fit();
+    assert!(ext.map.is_none());
+    assert_eq!(copy.len(), 1);
+}
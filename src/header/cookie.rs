@@ -0,0 +1,323 @@
+//! Parsing of the `Cookie` and `Set-Cookie` header fields.
+//!
+//! These functions split values into their parts as written. They do no
+//! decoding and apply none of the storage rules of [RFC 6265], such as
+//! domain matching or expiry; that is left to the caller.
+//!
+//! ```
+//! use http::header::HeaderValue;
+//! use http::header::cookie::{parse_cookie_header, SetCookie};
+//!
+//! let cookie = HeaderValue::from_static("session=abc123; theme=dark");
+//! let session = parse_cookie_header(&cookie).find(|&(name, _)| name == "session");
+//! assert_eq!(session, Some(("session", "abc123")));
+//!
+//! let set_cookie = HeaderValue::from_static("id=a3fWa; Max-Age=2592000; Secure; HttpOnly");
+//! let parsed = SetCookie::parse(&set_cookie).unwrap();
+//! assert_eq!(parsed.name(), "id");
+//! assert_eq!(parsed.max_age(), Some(2_592_000));
+//! assert!(parsed.secure());
+//! ```
+//!
+//! [RFC 6265]: https://www.rfc-editor.org/rfc/rfc6265
+
+use std::error::Error;
+use std::{fmt, str};
+
+use super::HeaderValue;
+
+/// Returns the name and value of each cookie in a `Cookie` header value.
+///
+/// Pairs are separated by `;`, with optional whitespace around each pair.
+/// A value wrapped in double quotes is returned without them. Segments
+/// without a `=`, with an empty name, or that aren't valid UTF-8 are
+/// skipped. Names are case-sensitive and may repeat.
+///
+/// # Examples
+///
+/// ```
+/// # use http::header::HeaderValue;
+/// use http::header::cookie::parse_cookie_header;
+///
+/// let val = HeaderValue::from_static(r#"a=1;b="two" ; junk; c="#);
+/// let cookies: Vec<_> = parse_cookie_header(&val).collect();
+/// assert_eq!(cookies, [("a", "1"), ("b", "two"), ("c", "")]);
+/// ```
+pub fn parse_cookie_header(value: &HeaderValue) -> impl Iterator<Item = (&str, &str)> {
+    value
+        .as_bytes()
+        .split(|&b| b == b';')
+        .filter_map(parse_pair)
+}
+
+/// The parts of a `Set-Cookie` header value.
+///
+/// Attributes are matched case-insensitively. If an attribute appears more
+/// than once, the last one wins. Unknown attributes, and known ones whose
+/// value is missing or malformed, are ignored.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SetCookie<'a> {
+    name: &'a str,
+    value: &'a str,
+    path: Option<&'a str>,
+    domain: Option<&'a str>,
+    max_age: Option<i64>,
+    expires: Option<&'a str>,
+    secure: bool,
+    http_only: bool,
+    same_site: Option<&'a str>,
+}
+
+/// An error returned when a `Set-Cookie` value has no valid name-value pair.
+#[derive(Clone)]
+pub struct InvalidSetCookie {
+    _priv: (),
+}
+
+impl<'a> SetCookie<'a> {
+    /// Parses a `Set-Cookie` header value.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the value doesn't start with a `name=value` pair
+    /// with a non-empty name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::header::HeaderValue;
+    /// use http::header::cookie::SetCookie;
+    ///
+    /// let val = HeaderValue::from_static("lang=en; Path=/docs; SameSite=Lax; Bogus");
+    /// let cookie = SetCookie::parse(&val).unwrap();
+    /// assert_eq!(cookie.name(), "lang");
+    /// assert_eq!(cookie.value(), "en");
+    /// assert_eq!(cookie.path(), Some("/docs"));
+    /// assert_eq!(cookie.same_site(), Some("Lax"));
+    /// assert!(!cookie.http_only());
+    ///
+    /// assert!(SetCookie::parse(&HeaderValue::from_static("Secure")).is_err());
+    /// ```
+    pub fn parse(value: &'a HeaderValue) -> Result<Self, InvalidSetCookie> {
+        let mut segments = value.as_bytes().split(|&b| b == b';');
+        let (name, value) = segments
+            .next()
+            .and_then(parse_pair)
+            .ok_or(InvalidSetCookie { _priv: () })?;
+
+        let mut cookie = SetCookie {
+            name,
+            value,
+            path: None,
+            domain: None,
+            max_age: None,
+            expires: None,
+            secure: false,
+            http_only: false,
+            same_site: None,
+        };
+
+        for segment in segments {
+            let Ok(segment) = str::from_utf8(segment.trim_ascii()) else {
+                continue;
+            };
+            let (attr, value) = match segment.split_once('=') {
+                Some((attr, value)) => (attr.trim_end(), Some(value.trim_start())),
+                None => (segment, None),
+            };
+
+            if attr.eq_ignore_ascii_case("secure") {
+                cookie.secure = true;
+            } else if attr.eq_ignore_ascii_case("httponly") {
+                cookie.http_only = true;
+            } else if let Some(value) = value.filter(|v| !v.is_empty()) {
+                if attr.eq_ignore_ascii_case("path") {
+                    cookie.path = Some(value);
+                } else if attr.eq_ignore_ascii_case("domain") {
+                    cookie.domain = Some(value);
+                } else if attr.eq_ignore_ascii_case("max-age") {
+                    if let Ok(max_age) = value.parse() {
+                        cookie.max_age = Some(max_age);
+                    }
+                } else if attr.eq_ignore_ascii_case("expires") {
+                    cookie.expires = Some(value);
+                } else if attr.eq_ignore_ascii_case("samesite") {
+                    cookie.same_site = Some(value);
+                }
+            }
+        }
+
+        Ok(cookie)
+    }
+
+    /// Returns the cookie's name.
+    #[must_use]
+    pub const fn name(&self) -> &'a str {
+        self.name
+    }
+
+    /// Returns the cookie's value, without any surrounding double quotes.
+    #[must_use]
+    pub const fn value(&self) -> &'a str {
+        self.value
+    }
+
+    /// Returns the `Path` attribute.
+    #[must_use]
+    pub const fn path(&self) -> Option<&'a str> {
+        self.path
+    }
+
+    /// Returns the `Domain` attribute as written, including any leading `.`.
+    #[must_use]
+    pub const fn domain(&self) -> Option<&'a str> {
+        self.domain
+    }
+
+    /// Returns the `Max-Age` attribute in seconds. It may be zero or negative,
+    /// both of which mean the cookie has expired.
+    #[must_use]
+    pub const fn max_age(&self) -> Option<i64> {
+        self.max_age
+    }
+
+    /// Returns the `Expires` attribute as written. The date isn't parsed.
+    #[must_use]
+    pub const fn expires(&self) -> Option<&'a str> {
+        self.expires
+    }
+
+    /// Returns `true` if the `Secure` attribute is present.
+    #[must_use]
+    pub const fn secure(&self) -> bool {
+        self.secure
+    }
+
+    /// Returns `true` if the `HttpOnly` attribute is present.
+    #[must_use]
+    pub const fn http_only(&self) -> bool {
+        self.http_only
+    }
+
+    /// Returns the `SameSite` attribute as written, such as `Strict`, `Lax` or
+    /// `None`.
+    #[must_use]
+    pub const fn same_site(&self) -> Option<&'a str> {
+        self.same_site
+    }
+}
+
+fn parse_pair(segment: &[u8]) -> Option<(&str, &str)> {
+    let segment = str::from_utf8(segment.trim_ascii()).ok()?;
+    let (name, value) = segment.split_once('=')?;
+    let (name, value) = (name.trim_end(), value.trim_start());
+
+    if name.is_empty() {
+        return None;
+    }
+
+    let value = value
+        .strip_prefix('"')
+        .and_then(|v| v.strip_suffix('"'))
+        .unwrap_or(value);
+
+    Some((name, value))
+}
+
+impl fmt::Debug for InvalidSetCookie {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("InvalidSetCookie")
+            // skip _priv noise
+            .finish()
+    }
+}
+
+impl fmt::Display for InvalidSetCookie {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("invalid Set-Cookie header value")
+    }
+}
+
+impl Error for InvalidSetCookie {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cookies(value: &'static str) -> Vec<String> {
+        let value = HeaderValue::from_static(value);
+        parse_cookie_header(&value)
+            .map(|(name, value)| format!("{name}={value}"))
+            .collect()
+    }
+
+    #[test]
+    fn cookie_header() {
+        assert_eq!(cookies("a=1"), ["a=1"]);
+        assert_eq!(cookies("a=1; b=2;c=3 ;  d=4"), ["a=1", "b=2", "c=3", "d=4"]);
+        assert_eq!(cookies("a=1; a=2"), ["a=1", "a=2"]);
+        assert_eq!(cookies("empty=; eq=a=b"), ["empty=", "eq=a=b"]);
+        assert_eq!(
+            cookies(r#"q="quoted"; half="open"#),
+            ["q=quoted", "half=\"open"]
+        );
+        assert_eq!(cookies(r#"q="""#), ["q="]);
+        assert_eq!(cookies("; novalue; =anon; a=1;"), ["a=1"]);
+        assert!(cookies("").is_empty());
+
+        let value = HeaderValue::from_bytes(b"bad=\xff; good=1").unwrap();
+        assert_eq!(
+            parse_cookie_header(&value).collect::<Vec<_>>(),
+            [("good", "1")]
+        );
+    }
+
+    #[test]
+    fn set_cookie() {
+        let value = HeaderValue::from_static(
+            "sid=\"x1\"; Path=/; Domain=.example.com; Max-Age=-1; \
+             Expires=Wed, 21 Oct 2015 07:28:00 GMT; Secure; HttpOnly; SameSite=Strict",
+        );
+        let cookie = SetCookie::parse(&value).unwrap();
+        assert_eq!(cookie.name(), "sid");
+        assert_eq!(cookie.value(), "x1");
+        assert_eq!(cookie.path(), Some("/"));
+        assert_eq!(cookie.domain(), Some(".example.com"));
+        assert_eq!(cookie.max_age(), Some(-1));
+        assert_eq!(cookie.expires(), Some("Wed, 21 Oct 2015 07:28:00 GMT"));
+        assert!(cookie.secure());
+        assert!(cookie.http_only());
+        assert_eq!(cookie.same_site(), Some("Strict"));
+    }
+
+    #[test]
+    fn set_cookie_attributes_are_lenient() {
+        let value = HeaderValue::from_static(
+            "a=b;path=/one; PATH = /two;max-age=soon;Domain=;secure=yes;httponly;Unknown=1;;",
+        );
+        let cookie = SetCookie::parse(&value).unwrap();
+        assert_eq!(cookie.value(), "b");
+        assert_eq!(cookie.path(), Some("/two"));
+        assert_eq!(cookie.max_age(), None);
+        assert_eq!(cookie.domain(), None);
+        assert_eq!(cookie.expires(), None);
+        assert!(cookie.secure());
+        assert!(cookie.http_only());
+        assert_eq!(cookie.same_site(), None);
+
+        let value = HeaderValue::from_static("a=");
+        let cookie = SetCookie::parse(&value).unwrap();
+        assert_eq!((cookie.name(), cookie.value()), ("a", ""));
+        assert!(!cookie.secure());
+    }
+
+    #[test]
+    fn set_cookie_requires_a_pair() {
+        for value in ["", "Secure", "=value; Path=/", " ; a=b"] {
+            assert!(
+                SetCookie::parse(&HeaderValue::from_static(value)).is_err(),
+                "{value:?}"
+            );
+        }
+    }
+}
@@ -0,0 +1,367 @@
+//! Parsing and formatting of HTTP-dates, as used by `Date`, `Expires`,
+//! `Last-Modified` and `Retry-After`.
+//!
+//! [RFC 9110] defines the preferred IMF-fixdate format, which is always used
+//! when formatting, as well as two obsolete formats that recipients must still
+//! accept:
+//!
+//! ```text
+//! Sun, 06 Nov 1994 08:49:37 GMT    ; IMF-fixdate
+//! Sunday, 06-Nov-94 08:49:37 GMT   ; obsolete RFC 850 format
+//! Sun Nov  6 08:49:37 1994         ; ANSI C's asctime() format
+//! ```
+//!
+//! [RFC 9110]: https://www.rfc-editor.org/rfc/rfc9110#section-5.6.7
+
+use std::error::Error;
+use std::fmt;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use bytes::Bytes;
+
+use super::HeaderValue;
+
+/// An error returned when a header value isn't a valid HTTP-date.
+#[derive(Clone)]
+pub struct InvalidHttpDate {
+    _priv: (),
+}
+
+/// Parses a header value as an HTTP-date, in any of the three formats.
+///
+/// The day name must match the date. Two-digit years of the RFC 850 format
+/// are read as 1970 to 2069.
+///
+/// # Errors
+///
+/// Returns an error if the value isn't a valid HTTP-date.
+///
+/// # Examples
+///
+/// ```
+/// # use http::header::HeaderValue;
+/// use http::header::http_date::parse_http_date;
+/// use std::time::{Duration, UNIX_EPOCH};
+///
+/// let val = HeaderValue::from_static("Sun, 06 Nov 1994 08:49:37 GMT");
+/// let date = parse_http_date(&val).unwrap();
+/// assert_eq!(date, UNIX_EPOCH + Duration::from_secs(784_111_777));
+///
+/// let val = HeaderValue::from_static("Sun Nov  6 08:49:37 1994");
+/// assert_eq!(parse_http_date(&val).unwrap(), date);
+/// ```
+pub fn parse_http_date(value: &HeaderValue) -> Result<SystemTime, InvalidHttpDate> {
+    parse(value.as_bytes()).ok_or(InvalidHttpDate { _priv: () })
+}
+
+/// Formats a time as an IMF-fixdate header value.
+///
+/// Fractions of a second are dropped. Times outside of the years 0 to 9999,
+/// which can't be written in four digits, are clamped to that range.
+///
+/// # Examples
+///
+/// ```
+/// use http::header::http_date::fmt_http_date;
+/// use std::time::{Duration, UNIX_EPOCH};
+///
+/// let date = UNIX_EPOCH + Duration::from_secs(784_111_777);
+/// assert_eq!(fmt_http_date(date), "Sun, 06 Nov 1994 08:49:37 GMT");
+/// ```
+#[must_use]
+pub fn fmt_http_date(time: SystemTime) -> HeaderValue {
+    let secs = match time.duration_since(UNIX_EPOCH) {
+        Ok(since) => i64::try_from(since.as_secs()).unwrap_or(i64::MAX),
+        Err(err) => {
+            let before = err.duration();
+            i64::try_from(before.as_secs())
+                .map_or(i64::MIN, |s| -s - i64::from(before.subsec_nanos() > 0))
+        }
+    };
+    let min = days_from_civil(0, 1, 1) * SECS_PER_DAY;
+    let max = days_from_civil(9999, 12, 31) * SECS_PER_DAY + SECS_PER_DAY - 1;
+    let secs = secs.clamp(min, max);
+
+    let days = secs.div_euclid(SECS_PER_DAY);
+    let time = secs.rem_euclid(SECS_PER_DAY);
+    let (year, month, day) = civil_from_days(days);
+
+    let date = format!(
+        "{}, {:02} {} {:04} {:02}:{:02}:{:02} GMT",
+        DAY_NAMES[weekday(days)],
+        day,
+        MONTHS[usize::try_from(month - 1).unwrap()],
+        year,
+        time / 3600,
+        time / 60 % 60,
+        time % 60,
+    );
+    HeaderValue::from_maybe_shared(Bytes::from(date)).expect("HTTP-dates are visible ASCII")
+}
+
+const SECS_PER_DAY: i64 = 86_400;
+
+const DAY_NAMES: [&str; 7] = ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"];
+const LONG_DAY_NAMES: [&str; 7] = [
+    "Sunday",
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday",
+];
+const MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+fn parse(src: &[u8]) -> Option<SystemTime> {
+    if !src.is_ascii() {
+        return None;
+    }
+    // Safety: ASCII is valid UTF-8.
+    let src = unsafe { std::str::from_utf8_unchecked(src) };
+
+    // After the day name each format is fixed width, and being ASCII, any
+    // offset is a char boundary.
+    let (day_name, day, month, year, time) = match src.len() {
+        // Sun, 06 Nov 1994 08:49:37 GMT
+        29 => {
+            expect(
+                src,
+                &[(3, ", "), (7, " "), (11, " "), (16, " "), (25, " GMT")],
+            )?;
+            let day_name = DAY_NAMES.iter().position(|&n| n == &src[..3])?;
+            let year = digits(&src[12..16])?;
+            (day_name, &src[5..7], &src[8..11], year, &src[17..25])
+        }
+        // Sun Nov  6 08:49:37 1994
+        24 => {
+            expect(src, &[(3, " "), (7, " "), (10, " "), (19, " ")])?;
+            let day_name = DAY_NAMES.iter().position(|&n| n == &src[..3])?;
+            let day = &src[8..10];
+            let day = day.strip_prefix(' ').unwrap_or(day);
+            let year = digits(&src[20..])?;
+            (day_name, day, &src[4..7], year, &src[11..19])
+        }
+        // Sunday, 06-Nov-94 08:49:37 GMT
+        _ => {
+            let (day_name, rest) = src.split_once(", ")?;
+            let day_name = LONG_DAY_NAMES.iter().position(|&n| n == day_name)?;
+            if rest.len() != 22 {
+                return None;
+            }
+            expect(rest, &[(2, "-"), (6, "-"), (9, " "), (18, " GMT")])?;
+            let year = match digits(&rest[7..9])? {
+                year @ 0..70 => 2000 + year,
+                year => 1900 + year,
+            };
+            (day_name, &rest[..2], &rest[3..6], year, &rest[10..18])
+        }
+    };
+
+    let month = i64::try_from(MONTHS.iter().position(|&m| m == month)?).ok()? + 1;
+    let day = digits(day)?;
+    if day < 1 || day > days_in_month(year, month) {
+        return None;
+    }
+
+    expect(time, &[(2, ":"), (5, ":")])?;
+    let (hour, minute, second) = (
+        digits(&time[..2])?,
+        digits(&time[3..5])?,
+        digits(&time[6..])?,
+    );
+    if hour > 23 || minute > 59 || second > 59 {
+        return None;
+    }
+
+    let days = days_from_civil(year, month, day);
+    if weekday(days) != day_name {
+        return None;
+    }
+
+    let secs = days * SECS_PER_DAY + hour * 3600 + minute * 60 + second;
+    let offset = Duration::from_secs(secs.unsigned_abs());
+    if secs >= 0 {
+        UNIX_EPOCH.checked_add(offset)
+    } else {
+        UNIX_EPOCH.checked_sub(offset)
+    }
+}
+
+/// Checks that `src` has each literal at its offset.
+fn expect(src: &str, literals: &[(usize, &str)]) -> Option<()> {
+    literals
+        .iter()
+        .all(|&(at, lit)| src.get(at..at + lit.len()) == Some(lit))
+        .then_some(())
+}
+
+fn digits(s: &str) -> Option<i64> {
+    if s.is_empty() || !s.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    s.parse().ok()
+}
+
+const fn is_leap_year(year: i64) -> bool {
+    year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+}
+
+const fn days_in_month(year: i64, month: i64) -> i64 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// The index into `DAY_NAMES` of a day counted from 1970-01-01, a Thursday.
+const fn weekday(days: i64) -> usize {
+    (days + 4).rem_euclid(7) as usize
+}
+
+// Conversions between civil dates and days since 1970-01-01, from
+// http://howardhinnant.github.io/date_algorithms.html
+
+const fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+const fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+impl fmt::Debug for InvalidHttpDate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("InvalidHttpDate")
+            // skip _priv noise
+            .finish()
+    }
+}
+
+impl fmt::Display for InvalidHttpDate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("invalid HTTP-date")
+    }
+}
+
+impl Error for InvalidHttpDate {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(secs: i64) -> SystemTime {
+        let offset = Duration::from_secs(secs.unsigned_abs());
+        if secs >= 0 {
+            UNIX_EPOCH + offset
+        } else {
+            UNIX_EPOCH - offset
+        }
+    }
+
+    fn parse(value: &'static str) -> Option<SystemTime> {
+        parse_http_date(&HeaderValue::from_static(value)).ok()
+    }
+
+    #[test]
+    fn formats() {
+        let date = Some(at(784_111_777));
+        assert_eq!(parse("Sun, 06 Nov 1994 08:49:37 GMT"), date);
+        assert_eq!(parse("Sunday, 06-Nov-94 08:49:37 GMT"), date);
+        assert_eq!(parse("Sun Nov  6 08:49:37 1994"), date);
+        assert_eq!(parse("Sun Nov 06 08:49:37 1994"), date);
+
+        assert_eq!(parse("Thursday, 01-Jan-70 00:00:00 GMT"), Some(UNIX_EPOCH));
+        assert_eq!(
+            parse("Saturday, 31-Dec-69 23:59:59 GMT"),
+            parse("Sat, 31 Dec 2069 23:59:59 GMT")
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        // Every day from 1900 to 2100, at a time that varies with the day.
+        let start = days_from_civil(1900, 1, 1);
+        let end = days_from_civil(2100, 12, 31);
+        for days in start..=end {
+            let secs = days * SECS_PER_DAY + (days * 7919).rem_euclid(SECS_PER_DAY);
+            let value = fmt_http_date(at(secs));
+            assert_eq!(value.len(), 29, "{value:?}");
+            assert_eq!(parse_http_date(&value).unwrap(), at(secs), "{value:?}");
+        }
+    }
+
+    #[test]
+    fn calendar() {
+        assert_eq!(fmt_http_date(UNIX_EPOCH), "Thu, 01 Jan 1970 00:00:00 GMT");
+        assert_eq!(
+            fmt_http_date(at(951_782_400)),
+            "Tue, 29 Feb 2000 00:00:00 GMT"
+        );
+        assert_eq!(fmt_http_date(at(-1)), "Wed, 31 Dec 1969 23:59:59 GMT");
+        assert_eq!(
+            fmt_http_date(UNIX_EPOCH - Duration::from_millis(1)),
+            "Wed, 31 Dec 1969 23:59:59 GMT"
+        );
+        assert_eq!(
+            fmt_http_date(UNIX_EPOCH + Duration::from_millis(1999)),
+            "Thu, 01 Jan 1970 00:00:01 GMT"
+        );
+
+        assert!(parse("Tue, 29 Feb 2000 00:00:00 GMT").is_some());
+        assert!(parse("Thu, 29 Feb 2001 00:00:00 GMT").is_none());
+        assert!(parse("Thu, 29 Feb 1900 00:00:00 GMT").is_none());
+        assert!(parse("Sat, 31 Apr 2000 00:00:00 GMT").is_none());
+    }
+
+    #[test]
+    fn clamps_to_four_digit_years() {
+        let far = UNIX_EPOCH + Duration::from_secs(400_000_000_000);
+        assert_eq!(fmt_http_date(far), "Fri, 31 Dec 9999 23:59:59 GMT");
+        assert!(parse_http_date(&fmt_http_date(far)).is_ok());
+    }
+
+    #[test]
+    fn invalid() {
+        for value in [
+            "",
+            "Sun, 06 Nov 1994 08:49:37 gmt",
+            "Sun,  6 Nov 1994 08:49:37 GMT",
+            "Mon, 06 Nov 1994 08:49:37 GMT",
+            "Sun, 06 Nov 1994 08:49:60 GMT",
+            "Sun, 06 Nov 1994 08-49-37 GMT",
+            "Sun, 06 nov 1994 08:49:37 GMT",
+            "Sun, 00 Nov 1994 08:49:37 GMT",
+            "Sun, 06 Nov 1994 08:49:37 GMT ",
+            "Sun, 06-Nov-94 08:49:37 GMT",
+            "Sunday, 06-Nov-1994 08:49:37 GMT",
+            "Sun Nov  6 08:49:37 94",
+            "Sun Nov 6  08:49:37 1994",
+            "784111777",
+        ] {
+            assert_eq!(parse(value), None, "{value:?}");
+        }
+
+        let value = HeaderValue::from_bytes(b"Sun, 06 Nov 1994 08:49:\xc3\xa9 GMT").unwrap();
+        assert!(parse_http_date(&value).is_err());
+    }
+}
@@ -8,10 +8,12 @@ use std::iter::{FromIterator, FusedIterator};
 use std::marker::PhantomData;
 use std::{fmt, mem, ops, ptr, vec};
 
+use bytes::BytesMut;
+
 use crate::Error;
 
 use super::HeaderValue;
-use super::name::{HdrName, HeaderName, InvalidHeaderName};
+use super::name::{HdrName, HeaderName, InvalidHeaderName, MapHash};
 
 pub use self::as_header_name::AsHeaderName;
 pub use self::into_header_name::IntoHeaderName;
@@ -216,10 +218,24 @@ pub struct ValueDrain<'a, T> {
 }
 
 /// Error returned when max capacity of `HeaderMap` is exceeded
+#[derive(Clone)]
 pub struct MaxSizeReached {
     _priv: (),
 }
 
+/// Error returned when a header value is not a valid unsigned integer.
+#[derive(Clone)]
+pub struct InvalidHeaderInt {
+    _priv: (),
+}
+
+/// Error returned when a connection-specific header is present in an HTTP/2
+/// or later message.
+#[derive(Clone, PartialEq, Eq)]
+pub struct ConnectionSpecificHeader {
+    name: HeaderName,
+}
+
 /// Tracks the value iterator state
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 enum Cursor {
@@ -458,6 +474,283 @@ impl HeaderMap {
             danger: Danger::Green,
         }
     }
+
+    /// Returns the value associated with the key as a `&str`.
+    ///
+    /// This is shorthand for calling `get` followed by
+    /// [`HeaderValue::to_str`]. Returns `None` if there is no value associated
+    /// with the key, or if the first value contains bytes that are not visible
+    /// ASCII.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::{HeaderMap, HeaderValue};
+    /// # use http::header::{CONTENT_TYPE, HOST};
+    /// let mut map = HeaderMap::new();
+    /// assert!(map.get_str(&HOST).is_none());
+    ///
+    /// map.insert(HOST, "example.com".parse().unwrap());
+    /// assert_eq!(map.get_str(&HOST), Some("example.com"));
+    ///
+    /// map.insert(CONTENT_TYPE, HeaderValue::from_bytes(b"caf\xc3\xa9").unwrap());
+    /// assert!(map.get_str(&CONTENT_TYPE).is_none());
+    /// ```
+    pub fn get_str<K>(&self, key: &K) -> Option<&str>
+    where
+        K: AsHeaderName,
+    {
+        self.get(key)?.to_str().ok()
+    }
+
+    /// Returns a reference to the value associated with the key, or to an
+    /// empty value if there is none.
+    ///
+    /// This is like `get`, but for callers that always need a `&HeaderValue`.
+    /// The empty value is a shared static, so this never allocates.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::HeaderMap;
+    /// # use http::header::{ACCEPT, HOST};
+    /// let mut map = HeaderMap::new();
+    /// map.insert(HOST, "example.com".parse().unwrap());
+    ///
+    /// assert_eq!(map.get_or_empty(&HOST), "example.com");
+    /// assert!(map.get_or_empty(&ACCEPT).is_empty());
+    /// ```
+    pub fn get_or_empty<K>(&self, key: &K) -> &HeaderValue
+    where
+        K: AsHeaderName,
+    {
+        static EMPTY: HeaderValue = HeaderValue::from_static("");
+
+        self.get(key).unwrap_or(&EMPTY)
+    }
+
+    /// Returns the value associated with the key parsed as a `u64`.
+    ///
+    /// This is intended for numeric headers such as `Content-Length`. Returns
+    /// `None` if there is no value associated with the key, or if the first
+    /// value is not made up solely of ASCII digits, ignoring surrounding
+    /// whitespace, or does not fit in a `u64`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::HeaderMap;
+    /// # use http::header::{CONTENT_LENGTH, HOST};
+    /// let mut map = HeaderMap::new();
+    /// assert!(map.get_u64(&CONTENT_LENGTH).is_none());
+    ///
+    /// map.insert(CONTENT_LENGTH, "1024".parse().unwrap());
+    /// assert_eq!(map.get_u64(&CONTENT_LENGTH), Some(1024));
+    ///
+    /// map.insert(HOST, "example.com".parse().unwrap());
+    /// assert!(map.get_u64(&HOST).is_none());
+    /// ```
+    pub fn get_u64<K>(&self, key: &K) -> Option<u64>
+    where
+        K: AsHeaderName,
+    {
+        self.get_int(key)
+    }
+
+    /// Returns the value associated with the key parsed as an unsigned
+    /// integer.
+    ///
+    /// This is the same as [`get_u64`](Self::get_u64). Use
+    /// [`get_int_strict`](Self::get_int_strict) to tell a missing header
+    /// apart from a malformed one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::HeaderMap;
+    /// # use http::header::MAX_FORWARDS;
+    /// let mut map = HeaderMap::new();
+    /// map.insert(MAX_FORWARDS, "10".parse().unwrap());
+    /// assert_eq!(map.get_int(&MAX_FORWARDS), Some(10));
+    /// ```
+    pub fn get_int<K>(&self, key: &K) -> Option<u64>
+    where
+        K: AsHeaderName,
+    {
+        self.get_int_strict(key)?.ok()
+    }
+
+    /// Returns the value associated with the key parsed as an unsigned
+    /// integer, reporting malformed values.
+    ///
+    /// Returns `None` if there is no value associated with the key, and
+    /// `Some(Err(_))` if the first value is not made up solely of ASCII
+    /// digits, ignoring surrounding whitespace, or does not fit in a `u64`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::HeaderMap;
+    /// # use http::header::{AGE, CONTENT_LENGTH};
+    /// let mut map = HeaderMap::new();
+    /// assert!(map.get_int_strict(&CONTENT_LENGTH).is_none());
+    ///
+    /// map.insert(CONTENT_LENGTH, "1024".parse().unwrap());
+    /// assert_eq!(map.get_int_strict(&CONTENT_LENGTH).unwrap().unwrap(), 1024);
+    ///
+    /// map.insert(AGE, "-1".parse().unwrap());
+    /// assert!(map.get_int_strict(&AGE).unwrap().is_err());
+    /// ```
+    pub fn get_int_strict<K>(&self, key: &K) -> Option<Result<u64, InvalidHeaderInt>>
+    where
+        K: AsHeaderName,
+    {
+        let digits = self.get(key)?.as_bytes().trim_ascii();
+
+        if digits.is_empty() || !digits.iter().all(u8::is_ascii_digit) {
+            return Some(Err(InvalidHeaderInt { _priv: () }));
+        }
+
+        Some(
+            digits
+                .iter()
+                .try_fold(0u64, |n, &b| {
+                    n.checked_mul(10)?.checked_add(u64::from(b - b'0'))
+                })
+                .ok_or(InvalidHeaderInt { _priv: () }),
+        )
+    }
+
+    /// Consumes the map, returning a map without any sensitive values.
+    ///
+    /// Every value for which [`HeaderValue::is_sensitive`] returns `true` is
+    /// dropped. For keys with several values only the sensitive ones are
+    /// removed, and the remaining values keep their order. Keys left without
+    /// any values are removed entirely.
+    ///
+    /// Values are only sensitive if they were marked as such with
+    /// [`HeaderValue::set_sensitive`]. Headers such as `Authorization` are not
+    /// detected by name, so they must be marked when inserted for this to
+    /// make the map safe to log.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::{HeaderMap, HeaderValue};
+    /// # use http::header::{AUTHORIZATION, COOKIE, HOST};
+    /// let mut secret = HeaderValue::from_static("Bearer hunter2");
+    /// secret.set_sensitive(true);
+    ///
+    /// let mut map = HeaderMap::new();
+    /// map.insert(HOST, "example.com".parse().unwrap());
+    /// map.insert(AUTHORIZATION, secret.clone());
+    /// map.append(COOKIE, "a=1".parse().unwrap());
+    /// map.append(COOKIE, secret);
+    ///
+    /// let map = map.remove_sensitive();
+    /// assert_eq!(map.len(), 2);
+    /// assert!(!map.contains_key(&AUTHORIZATION));
+    /// assert_eq!(map.value_count(&COOKIE), 1);
+    /// assert_eq!(map[COOKIE], "a=1");
+    /// ```
+    #[must_use]
+    pub fn remove_sensitive(self) -> Self {
+        let mut map = Self::with_capacity(self.keys_len());
+        let mut name = None;
+
+        for (key, value) in self {
+            if key.is_some() {
+                name = key;
+            }
+
+            if !value.is_sensitive() {
+                if let Some(name) = &name {
+                    map.append(name, value);
+                }
+            }
+        }
+
+        map
+    }
+
+    /// Returns a copy of the map without any sensitive values.
+    ///
+    /// This is the non-consuming version of
+    /// [`remove_sensitive`](Self::remove_sensitive).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::{HeaderMap, HeaderValue};
+    /// # use http::header::{AUTHORIZATION, HOST};
+    /// let mut secret = HeaderValue::from_static("Bearer hunter2");
+    /// secret.set_sensitive(true);
+    ///
+    /// let mut map = HeaderMap::new();
+    /// map.insert(HOST, "example.com".parse().unwrap());
+    /// map.insert(AUTHORIZATION, secret);
+    ///
+    /// let loggable = map.clone_non_sensitive();
+    /// assert_eq!(loggable.len(), 1);
+    /// assert_eq!(map.len(), 2);
+    /// ```
+    #[must_use]
+    pub fn clone_non_sensitive(&self) -> Self {
+        let mut map = Self::with_capacity(self.keys_len());
+
+        for (name, value) in self {
+            if !value.is_sensitive() {
+                map.append(name, value.clone());
+            }
+        }
+
+        map
+    }
+
+    /// Returns the total length of all header names and values, plus
+    /// `entry_overhead` bytes for every header field.
+    pub(crate) fn encoded_len(&self, entry_overhead: usize) -> usize {
+        self.iter()
+            .map(|(name, value)| name.as_str().len() + value.len() + entry_overhead)
+            .sum()
+    }
+
+    /// Writes every header field as `name: value\r\n`, in iteration order.
+    pub(crate) fn write_http1(&self, dst: &mut BytesMut) {
+        for (name, value) in self {
+            dst.extend_from_slice(name.as_str().as_bytes());
+            dst.extend_from_slice(b": ");
+            dst.extend_from_slice(value.as_bytes());
+            dst.extend_from_slice(b"\r\n");
+        }
+    }
+
+    /// Checks that none of the connection-specific headers forbidden in
+    /// HTTP/2 and later (RFC 9113, Section 8.2.2) are present.
+    ///
+    /// `TE` is allowed as long as its value is `trailers`.
+    pub(crate) fn check_connection_specific(&self) -> Result<(), ConnectionSpecificHeader> {
+        for (name, value) in self {
+            if is_connection_specific(name, value) {
+                return Err(ConnectionSpecificHeader { name: name.clone() });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns true if the header field is connection-specific, and so not
+/// allowed in HTTP/2 and later (RFC 9113, Section 8.2.2).
+pub fn is_connection_specific(name: &HeaderName, value: &HeaderValue) -> bool {
+    match name.as_str() {
+        "connection" | "keep-alive" | "proxy-connection" | "transfer-encoding" | "upgrade" => true,
+        "te" => !value
+            .as_bytes()
+            .trim_ascii()
+            .eq_ignore_ascii_case(b"trailers"),
+        _ => false,
+    }
 }
 
 impl<T> Default for HeaderMap<T> {
@@ -877,6 +1170,63 @@ impl<T> HeaderMap<T> {
         key.find(self).is_some()
     }
 
+    /// Returns true if the map contains more than one value for the
+    /// specified key.
+    ///
+    /// This is cheaper than counting the values with `get_all`, and is useful
+    /// for rejecting headers that must not be repeated, such as
+    /// `Content-Length`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::HeaderMap;
+    /// # use http::header::CONTENT_LENGTH;
+    /// let mut map = HeaderMap::new();
+    /// assert!(!map.has_multiple(&CONTENT_LENGTH));
+    ///
+    /// map.insert(CONTENT_LENGTH, "10".parse().unwrap());
+    /// assert!(!map.has_multiple(&CONTENT_LENGTH));
+    ///
+    /// map.append(CONTENT_LENGTH, "20".parse().unwrap());
+    /// assert!(map.has_multiple(&"content-length"));
+    /// ```
+    pub fn has_multiple<K>(&self, key: &K) -> bool
+    where
+        K: AsHeaderName,
+    {
+        key.find(self)
+            .is_some_and(|(_, i)| self.entries[i].links.is_some())
+    }
+
+    /// Returns the number of values associated with the key.
+    ///
+    /// This is 0 if the key is absent. The values are counted by walking them,
+    /// so prefer [`has_multiple`](Self::has_multiple) to only check for
+    /// repeated values.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::HeaderMap;
+    /// # use http::header::SET_COOKIE;
+    /// let mut map = HeaderMap::new();
+    /// assert_eq!(map.value_count(&SET_COOKIE), 0);
+    ///
+    /// map.insert(SET_COOKIE, "a=1".parse().unwrap());
+    /// assert_eq!(map.value_count(&SET_COOKIE), 1);
+    ///
+    /// map.append(SET_COOKIE, "b=2".parse().unwrap());
+    /// map.append(SET_COOKIE, "c=3".parse().unwrap());
+    /// assert_eq!(map.value_count(&"set-cookie"), 3);
+    /// ```
+    pub fn value_count<K>(&self, key: &K) -> usize
+    where
+        K: AsHeaderName,
+    {
+        self.get_all(key).iter().count()
+    }
+
     /// An iterator visiting all key-value pairs.
     ///
     /// The iteration order is arbitrary, but consistent across platforms for
@@ -1183,7 +1533,7 @@ impl<T> HeaderMap<T> {
 
     fn try_entry2<K>(&mut self, key: K) -> Result<Entry<'_, T>, MaxSizeReached>
     where
-        K: Hash + Into<HeaderName>,
+        K: MapHash + Into<HeaderName>,
         HeaderName: PartialEq<K>,
     {
         // Ensure that there is space in the map
@@ -1297,7 +1647,7 @@ impl<T> HeaderMap<T> {
     #[inline]
     fn try_insert2<K>(&mut self, key: K, value: T) -> Result<Option<T>, MaxSizeReached>
     where
-        K: Hash + Into<HeaderName>,
+        K: MapHash + Into<HeaderName>,
         HeaderName: PartialEq<K>,
     {
         self.try_reserve_one()?;
@@ -1440,7 +1790,7 @@ impl<T> HeaderMap<T> {
     #[inline]
     fn try_append2<K>(&mut self, key: K, value: T) -> Result<bool, MaxSizeReached>
     where
-        K: Hash + Into<HeaderName>,
+        K: MapHash + Into<HeaderName>,
         HeaderName: PartialEq<K>,
     {
         self.try_reserve_one()?;
@@ -1477,7 +1827,7 @@ impl<T> HeaderMap<T> {
     #[inline]
     fn find<K>(&self, key: &K) -> Option<(usize, usize)>
     where
-        K: Hash + Into<HeaderName>,
+        K: MapHash + Into<HeaderName>,
         HeaderName: PartialEq<K>,
     {
         if self.entries.is_empty() {
@@ -1567,6 +1917,50 @@ impl<T> HeaderMap<T> {
         }
     }
 
+    /// Inserts the entries of `defaults` whose keys are absent from `self`.
+    ///
+    /// Keys that already have a value in `self` are left untouched, whatever
+    /// `defaults` holds for them. For absent keys every value in `defaults`
+    /// is copied, in order.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if capacity exceeds max `HeaderMap` capacity.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::HeaderMap;
+    /// # use http::header::{CACHE_CONTROL, CONTENT_TYPE};
+    /// let mut defaults = HeaderMap::new();
+    /// defaults.insert(CONTENT_TYPE, "application/octet-stream".parse().unwrap());
+    /// defaults.append(CACHE_CONTROL, "no-store".parse().unwrap());
+    /// defaults.append(CACHE_CONTROL, "private".parse().unwrap());
+    ///
+    /// let mut map = HeaderMap::new();
+    /// map.insert(CONTENT_TYPE, "text/html".parse().unwrap());
+    /// map.apply_defaults(&defaults);
+    ///
+    /// assert_eq!(map[CONTENT_TYPE], "text/html");
+    /// assert_eq!(map.value_count(&CACHE_CONTROL), 2);
+    /// ```
+    pub fn apply_defaults(&mut self, defaults: &Self)
+    where
+        T: Clone,
+    {
+        for name in defaults.keys() {
+            if let Entry::Vacant(entry) = self.entry(name) {
+                let mut values = defaults.get_all(name).iter();
+                if let Some(first) = values.next() {
+                    let mut entry = entry.insert_entry(first.clone());
+                    for value in values {
+                        entry.append(value.clone());
+                    }
+                }
+            }
+        }
+    }
+
     /// Remove an entry from the map.
     ///
     /// Warning: To avoid inconsistent state, extra values _must_ be removed
@@ -2179,9 +2573,37 @@ impl<T: PartialEq> PartialEq for HeaderMap<T> {
 
 impl<T: Eq> Eq for HeaderMap<T> {}
 
+/// Formats the map with every value of a header grouped under its name.
+///
+/// Headers with a single value show that value, while headers with multiple
+/// values show a list of them, e.g.
+/// `{"set-cookie": ["a=1", "b=2"], "content-type": "text/html"}`.
 impl<T: fmt::Debug> fmt::Debug for HeaderMap<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.debug_map().entries(self.iter()).finish()
+        struct Values<'a, T> {
+            map: &'a HeaderMap<T>,
+            idx: usize,
+        }
+
+        impl<T: fmt::Debug> fmt::Debug for Values<'_, T> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.debug_list()
+                    .entries(self.map.value_iter(Some(self.idx)))
+                    .finish()
+            }
+        }
+
+        let mut map = f.debug_map();
+
+        for (idx, bucket) in self.entries.iter().enumerate() {
+            if bucket.links.is_some() {
+                map.entry(&bucket.key, &Values { map: self, idx });
+            } else {
+                map.entry(&bucket.key, &bucket.value);
+            }
+        }
+
+        map.finish()
     }
 }
 
@@ -2195,10 +2617,8 @@ where
     /// Using the index operator will cause a panic if the header you're querying isn't set.
     #[inline]
     fn index(&self, index: K) -> &T {
-        self.get2(&index).map_or_else(
-            || panic!("no entry found for key {:?}", index.as_str()),
-            |val| val,
-        )
+        self.get2(&index)
+            .unwrap_or_else(|| panic!("no entry found for key {:?}", index.as_str()))
     }
 }
 
@@ -3595,6 +4015,50 @@ impl fmt::Display for MaxSizeReached {
 
 impl std::error::Error for MaxSizeReached {}
 
+impl fmt::Debug for InvalidHeaderInt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("InvalidHeaderInt")
+            // skip _priv noise
+            .finish()
+    }
+}
+
+impl fmt::Display for InvalidHeaderInt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("header value is not a valid unsigned integer")
+    }
+}
+
+impl std::error::Error for InvalidHeaderInt {}
+
+impl ConnectionSpecificHeader {
+    /// Returns the name of the offending header.
+    #[must_use]
+    pub const fn name(&self) -> &HeaderName {
+        &self.name
+    }
+}
+
+impl fmt::Debug for ConnectionSpecificHeader {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ConnectionSpecificHeader")
+            .field("name", &self.name)
+            .finish()
+    }
+}
+
+impl fmt::Display for ConnectionSpecificHeader {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "connection-specific header `{}` is not allowed in HTTP/2 and later",
+            self.name
+        )
+    }
+}
+
+impl std::error::Error for ConnectionSpecificHeader {}
+
 // ===== impl Utils =====
 
 #[inline]
@@ -3604,10 +4068,9 @@ const fn usable_capacity(cap: usize) -> usize {
 
 #[inline]
 fn to_raw_capacity(n: usize) -> usize {
-    n.checked_add(n / 3).map_or_else(
-        || panic!("requested capacity {n} too large: overflow while converting to raw capacity",),
-        |n| n,
-    )
+    n.checked_add(n / 3).unwrap_or_else(|| {
+        panic!("requested capacity {n} too large: overflow while converting to raw capacity")
+    })
 }
 
 #[inline]
@@ -3623,17 +4086,19 @@ const fn probe_distance(mask: Size, hash: HashValue, current: usize) -> usize {
 
 fn hash_elem_using<K>(danger: &Danger, k: &K) -> HashValue
 where
-    K: Hash + ?Sized,
+    K: MapHash + ?Sized,
 {
     use fnv::FnvHasher;
 
     const MASK: u64 = (MAX_SIZE as u64) - 1;
 
     let hash = if let Danger::Red(ref hasher) = *danger {
-        hasher.hash_one(k)
+        let mut h = hasher.build_hasher();
+        k.map_hash(&mut h);
+        h.finish()
     } else {
         let mut h = FnvHasher::default();
-        k.hash(&mut h);
+        k.map_hash(&mut h);
         h.finish()
     };
 
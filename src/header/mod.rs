@@ -70,17 +70,38 @@
 //! [`HashMap`]: https://doc.rust-lang.org/std/collections/struct.HashMap.html
 //! [Robin Hood hashing]: https://en.wikipedia.org/wiki/Hash_table#Robin_Hood_hashing
 
+#[cfg(feature = "cookies")]
+pub mod cookie;
+#[cfg(feature = "http-date")]
+pub mod http_date;
 mod map;
 mod name;
+#[cfg(feature = "http-date")]
+pub mod retry_after;
+#[cfg(feature = "simd")]
+mod simd;
 mod value;
 
 pub use self::map::{
-    AsHeaderName, Drain, Entry, GetAll, HeaderMap, IntoHeaderName, IntoIter, Iter, IterMut, Keys,
-    MaxSizeReached, OccupiedEntry, VacantEntry, ValueDrain, ValueIter, ValueIterMut, Values,
-    ValuesMut,
+    AsHeaderName, ConnectionSpecificHeader, Drain, Entry, GetAll, HeaderMap, IntoHeaderName,
+    IntoIter, InvalidHeaderInt, Iter, IterMut, Keys, MaxSizeReached, OccupiedEntry, VacantEntry,
+    ValueDrain, ValueIter, ValueIterMut, Values, ValuesMut,
 };
-pub use self::name::{HeaderName, InvalidHeaderName};
-pub use self::value::{HeaderValue, InvalidHeaderValue, ToStrError};
+pub use self::name::{CasePreservingHeaderName, HeaderName, InvalidHeaderName};
+pub use self::value::{
+    AsciiHeaderValue, Base64DecodeError, HeaderValue, HeaderValueBuilder, InvalidHeaderValue,
+    ParsedHeaderValue, ToStrError,
+};
+
+pub(crate) use self::map::is_connection_specific;
+pub(crate) use self::value::split_unquoted;
+
+/// Header fields sent after the body of a message.
+///
+/// HTTP/1.1 chunked transfer coding and HTTP/2 and HTTP/3 trailing HEADERS
+/// frames can carry trailers. They are represented with a regular
+/// `HeaderMap`; see [`Response::trailers`](crate::Response::trailers).
+pub type Trailers = HeaderMap<HeaderValue>;
 
 // Use header name constants
 #[rustfmt::skip]
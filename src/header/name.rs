@@ -1,5 +1,5 @@
 use crate::byte_str::ByteStr;
-use bytes::{Bytes, BytesMut};
+use bytes::{Buf, Bytes, BytesMut};
 
 use std::borrow::Borrow;
 use std::convert::TryFrom;
@@ -29,13 +29,13 @@ use std::str::FromStr;
 ///
 /// [`HeaderMap`]: struct.HeaderMap.html
 /// [`header`]: index.html
-#[derive(Clone, Eq, PartialEq, Hash)]
+#[derive(Clone, Eq, PartialEq)]
 pub struct HeaderName {
     inner: Repr<Custom>,
 }
 
 // Almost a full `HeaderName`
-#[derive(Debug, Hash)]
+#[derive(Debug)]
 pub struct HdrName<'a> {
     inner: Repr<MaybeLower<'a>>,
 }
@@ -57,7 +57,36 @@ struct MaybeLower<'a> {
     lower: bool,
 }
 
+/// An HTTP header field name that remembers the case it was provided in.
+///
+/// Custom header names are displayed exactly as provided, while standard
+/// header names are always displayed in their canonical lower case form.
+/// Comparison and hashing ignore case, in the same way as for `HeaderName`.
+///
+/// This is useful for transparent proxies which must not normalize extension
+/// headers.
+///
+/// # Examples
+///
+/// ```
+/// # use http::header::*;
+/// let name = CasePreservingHeaderName::from_bytes(b"X-Custom-Foo").unwrap();
+/// assert_eq!(name.as_str(), "X-Custom-Foo");
+/// assert_eq!(name.header_name().as_str(), "x-custom-foo");
+/// assert_eq!(name, "x-custom-foo".parse::<CasePreservingHeaderName>().unwrap());
+///
+/// let name = CasePreservingHeaderName::from_bytes(b"Content-Length").unwrap();
+/// assert_eq!(name.as_str(), "content-length");
+/// ```
+#[derive(Clone)]
+pub struct CasePreservingHeaderName {
+    name: HeaderName,
+    // Only set for custom header names which were not already lower case.
+    original: Option<ByteStr>,
+}
+
 /// A possible error when converting a `HeaderName` from another type.
+#[derive(Clone)]
 pub struct InvalidHeaderName {
     _priv: (),
 }
@@ -1005,7 +1034,7 @@ standard_headers! {
 // HEADER_CHARS maps every byte that is 128 or larger to 0 so everything that is
 // mapped by HEADER_CHARS, maps to a valid single-byte UTF-8 codepoint.
 #[rustfmt::skip]
-const HEADER_CHARS: [u8; 256] = [
+pub(super) const HEADER_CHARS: [u8; 256] = [
     //  0      1      2      3      4      5      6      7      8      9
         0,     0,     0,     0,     0,     0,     0,     0,     0,     0, //   x
         0,     0,     0,     0,     0,     0,     0,     0,     0,     0, //  1x
@@ -1039,7 +1068,7 @@ const HEADER_CHARS: [u8; 256] = [
 // HEADER_CHARS_H2 maps every byte that is 128 or larger to 0 so everything that is
 // mapped by HEADER_CHARS_H2, maps to a valid single-byte UTF-8 codepoint.
 #[rustfmt::skip]
-const HEADER_CHARS_H2: [u8; 256] = [
+pub(super) const HEADER_CHARS_H2: [u8; 256] = [
     //  0      1      2      3      4      5      6      7      8      9
         0,     0,     0,     0,     0,     0,     0,     0,     0,     0, //   x
         0,     0,     0,     0,     0,     0,     0,     0,     0,     0, //  1x
@@ -1078,8 +1107,12 @@ fn parse_hdr<'a>(
         0 => Err(InvalidHeaderName::new()),
         len @ 1..=SCRATCH_BUF_SIZE => {
             // Read from data into the buffer - transforming using `table` as we go
-            data.iter()
+            let (same, rest) = data.split_at(lowercase_prefix_len(data));
+            same.iter()
                 .zip(b.iter_mut())
+                .for_each(|(index, out)| *out = MaybeUninit::new(*index));
+            rest.iter()
+                .zip(b[same.len()..].iter_mut())
                 .for_each(|(index, out)| *out = MaybeUninit::new(table[*index as usize]));
             // Safety: len bytes of b were just initialized.
             let name: &'a [u8] = unsafe { slice_assume_init(&b[0..len]) };
@@ -1099,6 +1132,22 @@ fn parse_hdr<'a>(
     }
 }
 
+/// Returns the length of a prefix of `src` that both header name tables map
+/// to itself, which can be copied as-is.
+#[inline]
+#[cfg_attr(not(feature = "simd"), allow(clippy::missing_const_for_fn))]
+fn lowercase_prefix_len(src: &[u8]) -> usize {
+    #[cfg(feature = "simd")]
+    {
+        super::simd::lowercase_token_prefix(src)
+    }
+    #[cfg(not(feature = "simd"))]
+    {
+        let _ = src;
+        0
+    }
+}
+
 impl From<StandardHeader> for HdrName<'_> {
     fn from(hdr: StandardHeader) -> Self {
         HdrName {
@@ -1126,7 +1175,10 @@ impl HeaderName {
                 use bytes::BufMut;
                 let mut dst = BytesMut::with_capacity(buf.len());
 
-                for b in buf {
+                let (same, rest) = buf.split_at(lowercase_prefix_len(buf));
+                dst.put_slice(same);
+
+                for b in rest {
                     // HEADER_CHARS maps all bytes to valid single-byte UTF-8
                     let b = HEADER_CHARS[*b as usize];
 
@@ -1147,6 +1199,38 @@ impl HeaderName {
         }
     }
 
+    /// Converts the next `len` bytes of a `Buf` to an HTTP header name.
+    ///
+    /// This function normalizes the input, like [`HeaderName::from_bytes`].
+    /// The bytes may span several chunks of `buf`; they are only gathered into
+    /// a single buffer if they aren't already contiguous. The `len` bytes are
+    /// consumed from `buf` whether or not they are valid.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `buf` has fewer than `len` bytes remaining.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::header::*;
+    /// use bytes::Buf;
+    ///
+    /// let mut buf = (&b"Content-"[..]).chain(&b"Type: text/html"[..]);
+    /// let hdr = HeaderName::from_buf(&mut buf, 12).unwrap();
+    /// assert_eq!(CONTENT_TYPE, hdr);
+    /// assert_eq!(buf.remaining(), 11);
+    /// ```
+    pub fn from_buf<B: Buf>(buf: &mut B, len: usize) -> Result<Self, InvalidHeaderName> {
+        if buf.chunk().len() >= len {
+            let res = Self::from_bytes(&buf.chunk()[..len]);
+            buf.advance(len);
+            res
+        } else {
+            Self::from_bytes(&buf.copy_to_bytes(len))
+        }
+    }
+
     /// Converts a slice of bytes to an HTTP header name.
     ///
     /// This function expects the input to only contain lowercase characters.
@@ -1177,7 +1261,7 @@ impl HeaderName {
                 Ok(Custom(val).into())
             }
             Repr::Custom(MaybeLower { buf, lower: false }) => {
-                for &b in buf {
+                for &b in &buf[lowercase_prefix_len(buf)..] {
                     // HEADER_CHARS_H2 maps all bytes that are not valid single-byte
                     // UTF-8 to 0 so this check returns an error for invalid UTF-8.
                     if HEADER_CHARS_H2[b as usize] == 0 {
@@ -1329,6 +1413,15 @@ impl Borrow<str> for HeaderName {
     }
 }
 
+// Hashes the same as `str`, as required by the `Borrow<str>` impl. The
+// `HeaderMap` uses the cheaper `MapHash` instead.
+impl Hash for HeaderName {
+    #[inline]
+    fn hash<H: Hasher>(&self, hasher: &mut H) {
+        self.as_str().hash(hasher);
+    }
+}
+
 impl fmt::Debug for HeaderName {
     fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
         fmt::Debug::fmt(self.as_str(), fmt)
@@ -1503,6 +1596,136 @@ impl PartialEq<HeaderName> for &str {
     }
 }
 
+impl CasePreservingHeaderName {
+    /// Converts a slice of bytes to an HTTP header name, preserving the case
+    /// of custom header names.
+    pub fn from_bytes(src: &[u8]) -> Result<Self, InvalidHeaderName> {
+        let name = HeaderName::from_bytes(src)?;
+
+        let original = match name.inner {
+            Repr::Custom(ref custom) if custom.0.as_bytes() != src => {
+                let buf = Bytes::copy_from_slice(src);
+                // Safety: `HeaderName::from_bytes` only accepts ASCII bytes,
+                // so src is valid UTF-8.
+                Some(unsafe { ByteStr::from_utf8_unchecked(buf) })
+            }
+            _ => None,
+        };
+
+        Ok(Self { name, original })
+    }
+
+    /// Returns a `str` representation of the header, in the case it was
+    /// provided in.
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        self.original
+            .as_deref()
+            .unwrap_or_else(|| self.name.as_str())
+    }
+
+    /// Returns the normalized `HeaderName`.
+    #[inline]
+    #[must_use]
+    pub const fn header_name(&self) -> &HeaderName {
+        &self.name
+    }
+
+    /// Consumes this value, returning the normalized `HeaderName`.
+    #[inline]
+    #[must_use]
+    pub fn into_header_name(self) -> HeaderName {
+        self.name
+    }
+}
+
+impl FromStr for CasePreservingHeaderName {
+    type Err = InvalidHeaderName;
+
+    fn from_str(s: &str) -> Result<Self, InvalidHeaderName> {
+        Self::from_bytes(s.as_bytes())
+    }
+}
+
+impl<'a> TryFrom<&'a str> for CasePreservingHeaderName {
+    type Error = InvalidHeaderName;
+    #[inline]
+    fn try_from(s: &'a str) -> Result<Self, Self::Error> {
+        Self::from_bytes(s.as_bytes())
+    }
+}
+
+impl<'a> TryFrom<&'a [u8]> for CasePreservingHeaderName {
+    type Error = InvalidHeaderName;
+    #[inline]
+    fn try_from(s: &'a [u8]) -> Result<Self, Self::Error> {
+        Self::from_bytes(s)
+    }
+}
+
+impl From<HeaderName> for CasePreservingHeaderName {
+    fn from(name: HeaderName) -> Self {
+        Self {
+            name,
+            original: None,
+        }
+    }
+}
+
+impl From<CasePreservingHeaderName> for HeaderName {
+    fn from(name: CasePreservingHeaderName) -> Self {
+        name.name
+    }
+}
+
+impl AsRef<str> for CasePreservingHeaderName {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl PartialEq for CasePreservingHeaderName {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+    }
+}
+
+impl Eq for CasePreservingHeaderName {}
+
+impl PartialEq<HeaderName> for CasePreservingHeaderName {
+    #[inline]
+    fn eq(&self, other: &HeaderName) -> bool {
+        self.name == *other
+    }
+}
+
+impl PartialEq<CasePreservingHeaderName> for HeaderName {
+    #[inline]
+    fn eq(&self, other: &CasePreservingHeaderName) -> bool {
+        *self == other.name
+    }
+}
+
+impl Hash for CasePreservingHeaderName {
+    #[inline]
+    fn hash<H: Hasher>(&self, hasher: &mut H) {
+        self.name.hash(hasher);
+    }
+}
+
+impl fmt::Debug for CasePreservingHeaderName {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), fmt)
+    }
+}
+
+impl fmt::Display for CasePreservingHeaderName {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self.as_str(), fmt)
+    }
+}
+
 impl fmt::Debug for InvalidHeaderName {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("InvalidHeaderName")
@@ -1624,6 +1847,35 @@ impl Hash for Custom {
     }
 }
 
+// ===== MapHash =====
+
+/// The hash used by `HeaderMap`, which hashes standard headers by their
+/// discriminant. `HeaderName` and `HdrName` must agree.
+pub trait MapHash {
+    fn map_hash<H: Hasher>(&self, hasher: &mut H);
+}
+
+impl MapHash for HeaderName {
+    #[inline]
+    fn map_hash<H: Hasher>(&self, hasher: &mut H) {
+        self.inner.hash(hasher);
+    }
+}
+
+impl MapHash for HdrName<'_> {
+    #[inline]
+    fn map_hash<H: Hasher>(&self, hasher: &mut H) {
+        self.inner.hash(hasher);
+    }
+}
+
+impl<T: MapHash + ?Sized> MapHash for &T {
+    #[inline]
+    fn map_hash<H: Hasher>(&self, hasher: &mut H) {
+        (**self).map_hash(hasher);
+    }
+}
+
 // ===== MaybeLower =====
 
 impl Hash for MaybeLower<'_> {
@@ -1923,4 +2175,49 @@ mod tests {
         HeaderName::from_lowercase(&[0x1; 100]).unwrap_err();
         HeaderName::from_lowercase(&[0xFF; 100]).unwrap_err();
     }
+
+    #[test]
+    fn test_borrow_str_hash_map_lookup() {
+        use std::collections::HashMap;
+
+        let mut map = HashMap::<HeaderName, u32>::new();
+        assert_eq!(map.get("content-type"), None);
+
+        map.insert(CONTENT_TYPE, 1);
+        map.insert(HeaderName::from_static("x-custom"), 2);
+
+        assert_eq!(map.get("content-type"), Some(&1));
+        assert_eq!(map.get("x-custom"), Some(&2));
+        assert_eq!(map.get("Content-Type"), None);
+        assert_eq!(map.get(&CONTENT_TYPE), Some(&1));
+    }
+
+    #[test]
+    fn test_case_preserving_custom() {
+        let name = CasePreservingHeaderName::from_bytes(b"X-Forwarded-SOMETHING").unwrap();
+        assert_eq!(name.as_str(), "X-Forwarded-SOMETHING");
+        assert_eq!(name.to_string(), "X-Forwarded-SOMETHING");
+        assert_eq!(
+            name.header_name(),
+            &HeaderName::from_static("x-forwarded-something")
+        );
+
+        let lower = CasePreservingHeaderName::from_bytes(b"x-forwarded-something").unwrap();
+        assert!(lower.original.is_none());
+        assert_eq!(name, lower);
+    }
+
+    #[test]
+    fn test_case_preserving_standard() {
+        let name = CasePreservingHeaderName::from_bytes(b"Content-Type").unwrap();
+        assert!(name.original.is_none());
+        assert_eq!(name.as_str(), "content-type");
+        assert_eq!(name, CONTENT_TYPE);
+    }
+
+    #[test]
+    fn test_case_preserving_invalid() {
+        assert!(CasePreservingHeaderName::from_bytes(b"Bad Name").is_err());
+        assert!(CasePreservingHeaderName::from_bytes(b"").is_err());
+    }
 }
@@ -0,0 +1,215 @@
+//! Reading and writing the `Retry-After` header field.
+//!
+//! A `Retry-After` value is either a number of seconds to wait or an
+//! HTTP-date after which to retry, as described in [RFC 9110].
+//!
+//! [RFC 9110]: https://www.rfc-editor.org/rfc/rfc9110#section-10.2.3
+
+use std::error::Error;
+use std::fmt;
+use std::time::{Duration, SystemTime};
+
+use super::HeaderValue;
+use super::http_date::{fmt_http_date, parse_http_date};
+
+/// An error returned when a `Retry-After` value is neither a number of
+/// seconds nor an HTTP-date.
+#[derive(Clone)]
+pub struct RetryAfterError {
+    _priv: (),
+}
+
+/// Parses a `Retry-After` value into the time to wait from `now`.
+///
+/// A number of seconds is returned as-is, regardless of `now`. For an
+/// HTTP-date, `now` should be the sender's clock, such as the response's
+/// `Date` header read with [`parse_http_date`], to be safe from clock skew.
+/// The local clock works too. A date that has already passed
+/// means retrying right away, so it gives a zero duration.
+///
+/// # Errors
+///
+/// Returns an error if the value is neither a non-negative integer that fits
+/// in a `u64` nor a valid HTTP-date.
+///
+/// # Examples
+///
+/// ```
+/// # use http::header::HeaderValue;
+/// use http::header::retry_after::parse_retry_after;
+/// use std::time::{Duration, SystemTime};
+///
+/// let now = SystemTime::now();
+/// let delay = parse_retry_after(&HeaderValue::from_static("120"), now).unwrap();
+/// assert_eq!(delay, Duration::from_secs(120));
+///
+/// let past = HeaderValue::from_static("Fri, 31 Dec 1999 23:59:59 GMT");
+/// assert_eq!(parse_retry_after(&past, now).unwrap(), Duration::ZERO);
+///
+/// assert!(parse_retry_after(&HeaderValue::from_static("-1"), now).is_err());
+/// ```
+pub fn parse_retry_after(
+    value: &HeaderValue,
+    now: SystemTime,
+) -> Result<Duration, RetryAfterError> {
+    let bytes = value.as_bytes();
+
+    if !bytes.is_empty() && bytes.iter().all(u8::is_ascii_digit) {
+        // Only digits, so the only possible error is overflow.
+        return value
+            .to_str()
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .map(Duration::from_secs)
+            .ok_or(RetryAfterError { _priv: () });
+    }
+
+    let date = parse_http_date(value).map_err(|_| RetryAfterError { _priv: () })?;
+    Ok(date.duration_since(now).unwrap_or(Duration::ZERO))
+}
+
+/// Formats a delay as a `Retry-After` number of seconds.
+///
+/// Fractions of a second are rounded up, so that the client doesn't retry
+/// too early.
+///
+/// # Examples
+///
+/// ```
+/// use http::header::retry_after::retry_after_delay;
+/// use std::time::Duration;
+///
+/// assert_eq!(retry_after_delay(Duration::from_secs(30)), "30");
+/// assert_eq!(retry_after_delay(Duration::from_millis(1500)), "2");
+/// ```
+#[must_use]
+pub fn retry_after_delay(delay: Duration) -> HeaderValue {
+    let secs = delay.as_secs() + u64::from(delay.subsec_nanos() > 0);
+    HeaderValue::from(secs)
+}
+
+/// Formats a time as a `Retry-After` HTTP-date.
+///
+/// This is the same as [`fmt_http_date`].
+///
+/// # Examples
+///
+/// ```
+/// use http::header::retry_after::retry_after_date;
+/// use std::time::{Duration, UNIX_EPOCH};
+///
+/// let at = UNIX_EPOCH + Duration::from_secs(1_445_412_480);
+/// assert_eq!(retry_after_date(at), "Wed, 21 Oct 2015 07:28:00 GMT");
+/// ```
+#[must_use]
+pub fn retry_after_date(at: SystemTime) -> HeaderValue {
+    fmt_http_date(at)
+}
+
+impl fmt::Debug for RetryAfterError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RetryAfterError")
+            // skip _priv noise
+            .finish()
+    }
+}
+
+impl fmt::Display for RetryAfterError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("invalid Retry-After header value")
+    }
+}
+
+impl Error for RetryAfterError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::UNIX_EPOCH;
+
+    // Wed, 21 Oct 2015 07:28:00 GMT
+    fn now() -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(1_445_412_480)
+    }
+
+    fn parse(value: &'static str) -> Result<Duration, RetryAfterError> {
+        parse_retry_after(&HeaderValue::from_static(value), now())
+    }
+
+    #[test]
+    fn delay_seconds() {
+        assert_eq!(parse("0").unwrap(), Duration::ZERO);
+        assert_eq!(parse("120").unwrap(), Duration::from_secs(120));
+        assert_eq!(parse("007").unwrap(), Duration::from_secs(7));
+        assert_eq!(
+            parse("18446744073709551615").unwrap(),
+            Duration::from_secs(u64::MAX)
+        );
+    }
+
+    #[test]
+    fn http_date() {
+        assert_eq!(
+            parse("Wed, 21 Oct 2015 07:30:30 GMT").unwrap(),
+            Duration::from_secs(150)
+        );
+        assert_eq!(
+            parse("Wednesday, 21-Oct-15 08:28:00 GMT").unwrap(),
+            Duration::from_secs(3600)
+        );
+        assert_eq!(
+            parse("Thu Oct 22 07:28:00 2015").unwrap(),
+            Duration::from_secs(86_400)
+        );
+        assert_eq!(
+            parse("Wed, 21 Oct 2015 07:28:00 GMT").unwrap(),
+            Duration::ZERO
+        );
+        assert_eq!(
+            parse("Tue, 20 Oct 2015 07:28:00 GMT").unwrap(),
+            Duration::ZERO
+        );
+        assert_eq!(
+            parse("Thu, 01 Jan 1970 00:00:00 GMT").unwrap(),
+            Duration::ZERO
+        );
+    }
+
+    #[test]
+    fn invalid() {
+        for value in [
+            "",
+            "-1",
+            "+1",
+            "1.5",
+            " 1",
+            "18446744073709551616",
+            "soon",
+            "Wed, 21 Oct 2015 07:28:00 UTC",
+            "Thu, 21 Oct 2015 07:28:00 GMT",
+            "Wed, 21 Oct 15 07:28:00 GMT",
+            "Wed, 32 Oct 2015 07:28:00 GMT",
+            "Wed, 21 Oct 2015 24:00:00 GMT",
+            "wed, 21 oct 2015 07:28:00 GMT",
+        ] {
+            assert!(parse(value).is_err(), "{value:?}");
+        }
+
+        let value = HeaderValue::from_bytes(b"Wed, 21 Oct 2015 07:28:\xff\xff GMT").unwrap();
+        assert!(parse_retry_after(&value, now()).is_err());
+    }
+
+    #[test]
+    fn format() {
+        assert_eq!(retry_after_delay(Duration::ZERO), "0");
+        assert_eq!(retry_after_delay(Duration::from_secs(120)), "120");
+        assert_eq!(retry_after_delay(Duration::from_nanos(1)), "1");
+
+        let date = retry_after_date(now() + Duration::from_secs(150));
+        assert_eq!(date, "Wed, 21 Oct 2015 07:30:30 GMT");
+        assert_eq!(
+            parse_retry_after(&date, now()).unwrap(),
+            Duration::from_secs(150)
+        );
+    }
+}
@@ -0,0 +1,271 @@
+//! Word-at-a-time validation of header bytes, enabled by the `simd` feature.
+//!
+//! Every function here gives exactly the same answer as the byte-at-a-time
+//! loop it replaces. Targets with SSE2 check 16 bytes per step; all others
+//! check 8 bytes per step packed into a `u64`. Any remainder shorter than a
+//! word falls back to the scalar predicates.
+
+use super::value::{is_valid, is_visible_ascii};
+
+const ONES: u64 = u64::from_ne_bytes([0x01; 8]);
+const HIGHS: u64 = ONES * 0x80;
+const LOW7: u64 = ONES * 0x7f;
+
+/// Returns whether every byte is valid in a header value.
+pub(super) fn all_valid(bytes: &[u8]) -> bool {
+    all_chunks(bytes, wide::all_valid, |rest| {
+        all_chunks(
+            rest,
+            |c| swar::invalid(u64::from_ne_bytes(*c)) == 0,
+            |rest| rest.iter().all(|&b| is_valid(b)),
+        )
+    })
+}
+
+/// Returns whether every byte is visible ASCII or a tab.
+pub(super) fn all_visible_ascii(bytes: &[u8]) -> bool {
+    all_chunks(bytes, wide::all_visible_ascii, |rest| {
+        all_chunks(
+            rest,
+            |c| swar::not_visible_ascii(u64::from_ne_bytes(*c)) == 0,
+            |rest| rest.iter().all(|&b| is_visible_ascii(b)),
+        )
+    })
+}
+
+/// Returns the length of a prefix of `bytes` made only of `a-z`, `0-9` and
+/// `-`, which every header name table maps to themselves.
+///
+/// The prefix is a whole number of words, so it may stop short of the first
+/// other byte; the caller handles the rest with its table.
+pub(super) fn lowercase_token_prefix(bytes: &[u8]) -> usize {
+    let mut len = 0;
+
+    let mut chunks = bytes.chunks_exact(16);
+    for chunk in chunks.by_ref() {
+        if !wide::all_lowercase_token(chunk.try_into().unwrap()) {
+            return len + word_prefix(chunk);
+        }
+        len += 16;
+    }
+
+    len + word_prefix(chunks.remainder())
+}
+
+fn word_prefix(bytes: &[u8]) -> usize {
+    bytes
+        .chunks_exact(8)
+        .take_while(|c| {
+            swar::not_lowercase_token(u64::from_ne_bytes((*c).try_into().unwrap())) == 0
+        })
+        .count()
+        * 8
+}
+
+/// Checks whole `N` byte chunks with `wide` and any remainder with `narrow`.
+fn all_chunks<const N: usize>(
+    bytes: &[u8],
+    wide: impl Fn(&[u8; N]) -> bool,
+    narrow: impl FnOnce(&[u8]) -> bool,
+) -> bool {
+    let mut chunks = bytes.chunks_exact(N);
+    chunks.by_ref().all(|c| wide(c.try_into().unwrap())) && narrow(chunks.remainder())
+}
+
+/// Byte-wise comparisons on a `u64`, each setting the high bit of exactly
+/// the bytes that match. Masking to the low seven bits first keeps one byte's
+/// carry from spilling into the next.
+mod swar {
+    use super::{HIGHS, LOW7, ONES};
+
+    /// Bytes less than `n`, where `n <= 128`.
+    const fn lt(x: u64, n: u8) -> u64 {
+        let ge_low7 = (x & LOW7) + ONES * (0x80 - n as u64);
+        !(ge_low7 | x) & HIGHS
+    }
+
+    const fn eq(x: u64, c: u8) -> u64 {
+        let y = x ^ (ONES * c as u64);
+        !(((y & LOW7) + LOW7) | y) & HIGHS
+    }
+
+    const fn in_range(x: u64, lo: u8, hi: u8) -> u64 {
+        lt(x, hi + 1) & !lt(x, lo)
+    }
+
+    const fn control(x: u64) -> u64 {
+        lt(x, 32) & !eq(x, b'\t')
+    }
+
+    pub(super) const fn invalid(x: u64) -> u64 {
+        control(x) | eq(x, 127)
+    }
+
+    pub(super) const fn not_visible_ascii(x: u64) -> u64 {
+        control(x) | eq(x, 127) | (x & HIGHS)
+    }
+
+    pub(super) const fn not_lowercase_token(x: u64) -> u64 {
+        let ok = in_range(x, b'a', b'z') | in_range(x, b'0', b'9') | eq(x, b'-');
+        !ok & HIGHS
+    }
+}
+
+#[cfg(all(
+    any(target_arch = "x86", target_arch = "x86_64"),
+    target_feature = "sse2"
+))]
+mod wide {
+    #[cfg(target_arch = "x86")]
+    use std::arch::x86::{
+        __m128i, _mm_andnot_si128, _mm_cmpeq_epi8, _mm_loadu_si128, _mm_max_epu8, _mm_min_epu8,
+        _mm_movemask_epi8, _mm_or_si128, _mm_set1_epi8,
+    };
+    #[cfg(target_arch = "x86_64")]
+    use std::arch::x86_64::{
+        __m128i, _mm_andnot_si128, _mm_cmpeq_epi8, _mm_loadu_si128, _mm_max_epu8, _mm_min_epu8,
+        _mm_movemask_epi8, _mm_or_si128, _mm_set1_epi8,
+    };
+
+    // SSE2 only compares signed bytes, so unsigned `b <= n` is written as
+    // `min(b, n) == b` and `b >= n` as `max(b, n) == b`.
+
+    pub(super) fn all_valid(chunk: &[u8; 16]) -> bool {
+        // Safety: SSE2 is enabled for this target, and the load is
+        // unaligned and reads exactly the 16 bytes of `chunk`.
+        unsafe {
+            let v = _mm_loadu_si128(chunk.as_ptr().cast());
+            let bad = _mm_or_si128(control(v), _mm_cmpeq_epi8(v, _mm_set1_epi8(127)));
+            _mm_movemask_epi8(bad) == 0
+        }
+    }
+
+    pub(super) fn all_visible_ascii(chunk: &[u8; 16]) -> bool {
+        // Safety: see `all_valid`.
+        unsafe {
+            let v = _mm_loadu_si128(chunk.as_ptr().cast());
+            let high = _mm_cmpeq_epi8(_mm_max_epu8(v, _mm_set1_epi8(127)), v);
+            _mm_movemask_epi8(_mm_or_si128(control(v), high)) == 0
+        }
+    }
+
+    pub(super) fn all_lowercase_token(chunk: &[u8; 16]) -> bool {
+        // Safety: see `all_valid`.
+        unsafe {
+            let v = _mm_loadu_si128(chunk.as_ptr().cast());
+            let ok = _mm_or_si128(
+                _mm_or_si128(in_range(v, b'a', b'z'), in_range(v, b'0', b'9')),
+                _mm_cmpeq_epi8(v, _mm_set1_epi8(b'-'.cast_signed())),
+            );
+            _mm_movemask_epi8(ok) == 0xffff
+        }
+    }
+
+    unsafe fn control(v: __m128i) -> __m128i {
+        unsafe {
+            let lt32 = _mm_cmpeq_epi8(_mm_min_epu8(v, _mm_set1_epi8(31)), v);
+            _mm_andnot_si128(_mm_cmpeq_epi8(v, _mm_set1_epi8(b'\t'.cast_signed())), lt32)
+        }
+    }
+
+    unsafe fn in_range(v: __m128i, lo: u8, hi: u8) -> __m128i {
+        unsafe {
+            let clamped = _mm_min_epu8(
+                _mm_max_epu8(v, _mm_set1_epi8(lo.cast_signed())),
+                _mm_set1_epi8(hi.cast_signed()),
+            );
+            _mm_cmpeq_epi8(clamped, v)
+        }
+    }
+}
+
+#[cfg(not(all(
+    any(target_arch = "x86", target_arch = "x86_64"),
+    target_feature = "sse2"
+)))]
+mod wide {
+    use super::swar;
+
+    fn words(chunk: &[u8; 16]) -> [u64; 2] {
+        let (a, b) = chunk.split_at(8);
+        [
+            u64::from_ne_bytes(a.try_into().unwrap()),
+            u64::from_ne_bytes(b.try_into().unwrap()),
+        ]
+    }
+
+    pub(super) fn all_valid(chunk: &[u8; 16]) -> bool {
+        let [a, b] = words(chunk);
+        swar::invalid(a) | swar::invalid(b) == 0
+    }
+
+    pub(super) fn all_visible_ascii(chunk: &[u8; 16]) -> bool {
+        let [a, b] = words(chunk);
+        swar::not_visible_ascii(a) | swar::not_visible_ascii(b) == 0
+    }
+
+    pub(super) fn all_lowercase_token(chunk: &[u8; 16]) -> bool {
+        let [a, b] = words(chunk);
+        swar::not_lowercase_token(a) | swar::not_lowercase_token(b) == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn is_lowercase_token(b: u8) -> bool {
+        b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'-'
+    }
+
+    // Every byte value at every position of every window length up to a few
+    // words, so each of the wide, word and scalar paths sees it.
+    fn each_window(base: u8, mut f: impl FnMut(&[u8])) {
+        for len in 1..=40 {
+            for pos in 0..len {
+                for b in 0..=255 {
+                    let mut window = vec![base; len];
+                    window[pos] = b;
+                    f(&window);
+                }
+            }
+        }
+        f(&[]);
+    }
+
+    #[test]
+    fn all_valid_matches_scalar() {
+        each_window(b'a', |w| {
+            assert_eq!(all_valid(w), w.iter().all(|&b| is_valid(b)), "{w:?}");
+        });
+    }
+
+    #[test]
+    fn all_visible_ascii_matches_scalar() {
+        each_window(b'a', |w| {
+            assert_eq!(
+                all_visible_ascii(w),
+                w.iter().all(|&b| is_visible_ascii(b)),
+                "{w:?}"
+            );
+        });
+    }
+
+    #[test]
+    fn lowercase_token_prefix_matches_scalar() {
+        each_window(b'a', |w| {
+            let exact = w.iter().take_while(|&&b| is_lowercase_token(b)).count();
+            assert_eq!(lowercase_token_prefix(w), exact / 8 * 8, "{w:?}");
+        });
+    }
+
+    #[test]
+    fn lowercase_tokens_map_to_themselves() {
+        use super::super::name::{HEADER_CHARS, HEADER_CHARS_H2};
+
+        for b in (0..=255).filter(|&b| is_lowercase_token(b)) {
+            assert_eq!(HEADER_CHARS[usize::from(b)], b);
+            assert_eq!(HEADER_CHARS_H2[usize::from(b)], b);
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use bytes::{Bytes, BytesMut};
+use bytes::{Buf, Bytes, BytesMut};
 
 use std::convert::TryFrom;
 use std::error::Error;
@@ -26,6 +26,7 @@ pub struct HeaderValue {
 
 /// A possible error when converting a `HeaderValue` from a string or byte
 /// slice.
+#[derive(Clone)]
 pub struct InvalidHeaderValue {
     _priv: (),
 }
@@ -34,11 +35,82 @@ pub struct InvalidHeaderValue {
 ///
 /// Header field values may contain opaque bytes, in which case it is not
 /// possible to represent the value as a string.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct ToStrError {
     _priv: (),
 }
 
+/// A possible error when decoding a `HeaderValue` as base64.
+#[derive(Debug, Clone)]
+pub struct Base64DecodeError {
+    _priv: (),
+}
+
+/// An element of a header value, as returned by
+/// [`HeaderValue::parse_directives`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ParsedHeaderValue {
+    /// A bare token, such as `public` or `text/html`.
+    Token(String),
+    /// A `name=value` pair, such as `max-age=3600` or `charset=utf-8`.
+    ///
+    /// A quoted value is unquoted, with escaped characters unescaped.
+    Pair(String, String),
+}
+
+/// A `HeaderValue` that is known to only contain visible ASCII characters.
+///
+/// This is returned by [`HeaderValue::as_ascii`], and can be passed where an
+/// `AsRef<str>` or a `&str` is expected. `HeaderValue` itself can't implement
+/// `AsRef<str>`, as header values may contain opaque bytes.
+///
+/// # Examples
+///
+/// ```
+/// # use http::header::HeaderValue;
+/// fn shout<S: AsRef<str>>(s: S) -> String {
+///     s.as_ref().to_uppercase()
+/// }
+///
+/// let value = HeaderValue::from_static("gzip");
+/// assert_eq!(shout(value.as_ascii().unwrap()), "GZIP");
+/// ```
+#[derive(Clone, Copy)]
+pub struct AsciiHeaderValue<'a> {
+    value: &'a HeaderValue,
+    s: &'a str,
+}
+
+/// Builds a `HeaderValue` incrementally.
+///
+/// `HeaderValueBuilder` implements [`fmt::Write`] and [`std::io::Write`], so
+/// complex values can be assembled with `write!` without an intermediate
+/// `String`. Every write is validated, and once an invalid byte has been
+/// written, [`finish`](Self::finish) returns an error.
+///
+/// # Examples
+///
+/// ```
+/// # use http::header::HeaderValueBuilder;
+/// use std::fmt::Write;
+///
+/// let mut builder = HeaderValueBuilder::new();
+/// for (i, rel) in ["prev", "next"].iter().enumerate() {
+///     if i > 0 {
+///         builder.write_str(", ").unwrap();
+///     }
+///     write!(builder, "</page/{}>; rel=\"{}\"", i + 1, rel).unwrap();
+/// }
+///
+/// let value = builder.finish().unwrap();
+/// assert_eq!(value, r#"</page/1>; rel="prev", </page/2>; rel="next""#);
+/// ```
+#[derive(Debug)]
+pub struct HeaderValueBuilder {
+    buf: BytesMut,
+    is_valid: bool,
+}
+
 impl HeaderValue {
     /// Convert a static string to a `HeaderValue`.
     ///
@@ -152,6 +224,43 @@ impl HeaderValue {
         name.into()
     }
 
+    /// Encode binary data as a standard base64 `HeaderValue`.
+    ///
+    /// The value uses the standard alphabet of RFC 4648 with padding, as
+    /// expected by headers such as `Authorization: Basic` and
+    /// `Sec-WebSocket-Key`. Base64 output is always a valid header value, so
+    /// this is infallible.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::header::HeaderValue;
+    /// let val = HeaderValue::from_base64(b"user:pass");
+    /// assert_eq!(val, "dXNlcjpwYXNz");
+    /// ```
+    #[must_use]
+    pub fn from_base64(data: &[u8]) -> Self {
+        let mut buf = Vec::with_capacity(data.len().div_ceil(3) * 4);
+        for chunk in data.chunks(3) {
+            let n = chunk
+                .iter()
+                .enumerate()
+                .fold(0u32, |n, (i, &b)| n | u32::from(b) << (16 - 8 * i));
+            for i in 0..4 {
+                if i <= chunk.len() {
+                    buf.push(BASE64_ALPHABET[(n >> (18 - 6 * i) & 0x3f) as usize]);
+                } else {
+                    buf.push(b'=');
+                }
+            }
+        }
+
+        Self {
+            inner: Bytes::from(buf),
+            is_sensitive: false,
+        }
+    }
+
     /// Attempt to convert a byte slice to a `HeaderValue`.
     ///
     /// If the argument contains invalid header value bytes, an error is
@@ -196,6 +305,31 @@ impl HeaderValue {
         Self::from_bytes(src.as_ref())
     }
 
+    /// Attempt to convert the next `len` bytes of a `Buf` to a `HeaderValue`.
+    ///
+    /// The bytes may span several chunks of `buf`. They are only copied if
+    /// they aren't already contiguous in a `Bytes` that can be shared. The
+    /// `len` bytes are consumed from `buf` whether or not they are valid.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `buf` has fewer than `len` bytes remaining.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::header::HeaderValue;
+    /// use bytes::Buf;
+    ///
+    /// let mut buf = (&b"text/"[..]).chain(&b"html; charset=utf-8"[..]);
+    /// let val = HeaderValue::from_buf(&mut buf, 9).unwrap();
+    /// assert_eq!(val, "text/html");
+    /// assert_eq!(buf.remaining(), 15);
+    /// ```
+    pub fn from_buf<B: Buf>(buf: &mut B, len: usize) -> Result<Self, InvalidHeaderValue> {
+        Self::from_shared(buf.copy_to_bytes(len))
+    }
+
     /// Convert a `Bytes` directly into a `HeaderValue` without validating.
     ///
     /// This function does NOT validate that illegal bytes are not contained
@@ -239,10 +373,8 @@ impl HeaderValue {
         src: T,
         into: F,
     ) -> Result<Self, InvalidHeaderValue> {
-        for &b in src.as_ref() {
-            if !is_valid(b) {
-                return Err(InvalidHeaderValue { _priv: () });
-            }
+        if !all_valid(src.as_ref()) {
+            return Err(InvalidHeaderValue { _priv: () });
         }
         Ok(Self {
             inner: into(src),
@@ -266,15 +398,119 @@ impl HeaderValue {
     pub fn to_str(&self) -> Result<&str, ToStrError> {
         let bytes = self.as_ref();
 
-        for &b in bytes {
-            if !is_visible_ascii(b) {
-                return Err(ToStrError { _priv: () });
-            }
+        if !all_visible_ascii(bytes) {
+            return Err(ToStrError { _priv: () });
         }
 
         unsafe { Ok(str::from_utf8_unchecked(bytes)) }
     }
 
+    /// Borrows the `HeaderValue` as an [`AsciiHeaderValue`] if it only
+    /// contains visible ASCII chars.
+    ///
+    /// Like [`to_str`](Self::to_str), this scans the whole value once. The
+    /// result can then be used as a `&str` without checking again.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::header::HeaderValue;
+    /// let val = HeaderValue::from_static("hello");
+    /// assert_eq!(val.as_ascii().unwrap().len(), 5);
+    ///
+    /// let val = HeaderValue::from_bytes(b"caf\xe9").unwrap();
+    /// assert!(val.as_ascii().is_err());
+    /// ```
+    pub fn as_ascii(&self) -> Result<AsciiHeaderValue<'_>, ToStrError> {
+        Ok(AsciiHeaderValue {
+            value: self,
+            s: self.to_str()?,
+        })
+    }
+
+    /// Parses the value as a list of tokens and `name=value` pairs,
+    /// separated by commas or semicolons.
+    ///
+    /// This covers both the comma separated directives of headers such as
+    /// `Cache-Control: public, max-age=3600`, and the semicolon separated
+    /// parameters of headers such as `Content-Type: text/html; charset=utf-8`.
+    /// Separators inside quoted strings are ignored, whitespace around
+    /// elements, names and values is trimmed, and empty elements are skipped.
+    ///
+    /// Names are returned as written; directive and parameter names are
+    /// usually case-insensitive. Bytes that aren't valid UTF-8 are replaced
+    /// with `U+FFFD`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::header::{HeaderValue, ParsedHeaderValue};
+    /// let val = HeaderValue::from_static(r#"text/html; charset="utf-8""#);
+    /// let directives: Vec<_> = val.parse_directives().collect();
+    /// assert_eq!(
+    ///     directives,
+    ///     [
+    ///         ParsedHeaderValue::Token("text/html".into()),
+    ///         ParsedHeaderValue::Pair("charset".into(), "utf-8".into()),
+    ///     ]
+    /// );
+    /// ```
+    pub fn parse_directives(&self) -> impl Iterator<Item = ParsedHeaderValue> + '_ {
+        split_unquoted(self.as_bytes(), |b| b == b',' || b == b';')
+            .map(<[u8]>::trim_ascii)
+            .filter(|element| !element.is_empty())
+            .map(parse_directive)
+    }
+
+    /// Decode the `HeaderValue` as standard base64.
+    ///
+    /// Both padded and unpadded input is accepted. Any character outside the
+    /// standard alphabet, including whitespace, results in an error, as do
+    /// non-zero trailing bits. Scheme prefixes such as `Basic ` are not
+    /// stripped.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `Base64DecodeError` if the value isn't valid base64.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::header::HeaderValue;
+    /// let val = HeaderValue::from_static("dXNlcjpwYXNz");
+    /// assert_eq!(val.to_base64_decoded().unwrap(), b"user:pass");
+    ///
+    /// let val = HeaderValue::from_static("Basic dXNlcjpwYXNz");
+    /// assert!(val.to_base64_decoded().is_err());
+    /// ```
+    pub fn to_base64_decoded(&self) -> Result<Vec<u8>, Base64DecodeError> {
+        const ERR: Base64DecodeError = Base64DecodeError { _priv: () };
+
+        let src = self.as_bytes();
+        let data = src
+            .strip_suffix(b"==")
+            .or_else(|| src.strip_suffix(b"="))
+            .unwrap_or(src);
+        // Padding is only valid to complete the final quad.
+        if (data.len() != src.len() && !src.len().is_multiple_of(4)) || data.len() % 4 == 1 {
+            return Err(ERR);
+        }
+
+        let mut out = Vec::with_capacity(data.len() / 4 * 3 + 2);
+        for chunk in data.chunks(4) {
+            let mut n = 0u32;
+            for (i, &b) in chunk.iter().enumerate() {
+                n |= u32::from(base64_value(b).ok_or(ERR)?) << (18 - 6 * i);
+            }
+            let len = chunk.len() - 1;
+            if n & ((1 << (24 - 8 * len)) - 1) != 0 {
+                return Err(ERR);
+            }
+            out.extend_from_slice(&n.to_be_bytes()[1..=len]);
+        }
+        Ok(out)
+    }
+
     /// Returns the length of `self`.
     ///
     /// This length is in bytes.
@@ -322,6 +558,56 @@ impl HeaderValue {
         self.as_ref()
     }
 
+    /// Returns a copy of this value with every ASCII letter lowercased.
+    ///
+    /// Bytes that aren't ASCII are left unchanged, and the copy keeps this
+    /// value's sensitivity. No new buffer is allocated if there is nothing to
+    /// change.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::header::HeaderValue;
+    /// let val = HeaderValue::from_static("CHUNKED");
+    /// assert_eq!(val.to_ascii_lowercase(), "chunked");
+    /// ```
+    #[must_use]
+    pub fn to_ascii_lowercase(&self) -> Self {
+        self.map_ascii(u8::is_ascii_uppercase, <[u8]>::make_ascii_lowercase)
+    }
+
+    /// Returns a copy of this value with every ASCII letter uppercased.
+    ///
+    /// Bytes that aren't ASCII are left unchanged, and the copy keeps this
+    /// value's sensitivity. No new buffer is allocated if there is nothing to
+    /// change.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::header::HeaderValue;
+    /// let val = HeaderValue::from_static("no-cache");
+    /// assert_eq!(val.to_ascii_uppercase(), "NO-CACHE");
+    /// ```
+    #[must_use]
+    pub fn to_ascii_uppercase(&self) -> Self {
+        self.map_ascii(u8::is_ascii_lowercase, <[u8]>::make_ascii_uppercase)
+    }
+
+    fn map_ascii(&self, changes: fn(&u8) -> bool, map: fn(&mut [u8])) -> Self {
+        if !self.as_bytes().iter().any(changes) {
+            return self.clone();
+        }
+
+        let mut buf = BytesMut::from(self.as_bytes());
+        map(&mut buf);
+        // Changing the case of ASCII letters keeps every byte valid.
+        Self {
+            inner: buf.freeze(),
+            is_sensitive: self.is_sensitive,
+        }
+    }
+
     /// Mark that the header value represents sensitive information.
     ///
     /// # Examples
@@ -371,6 +657,37 @@ impl HeaderValue {
     pub const fn is_sensitive(&self) -> bool {
         self.is_sensitive
     }
+
+    /// Compares two header values in constant time.
+    ///
+    /// Use this, rather than `==`, when comparing secrets such as API keys or
+    /// `Authorization` credentials. `==` returns as soon as a byte differs, so
+    /// the time it takes can reveal how much of a guess was correct.
+    ///
+    /// The running time depends only on the length of the values, so the
+    /// length of the secret is not hidden.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::header::HeaderValue;
+    /// let expected = HeaderValue::from_static("Bearer s3cr3t");
+    ///
+    /// assert!(expected.constant_time_eq(&HeaderValue::from_static("Bearer s3cr3t")));
+    /// assert!(!expected.constant_time_eq(&HeaderValue::from_static("Bearer guess!")));
+    /// ```
+    #[must_use]
+    pub fn constant_time_eq(&self, other: &Self) -> bool {
+        let (a, b) = (self.as_bytes(), other.as_bytes());
+
+        if a.len() != b.len() {
+            return false;
+        }
+
+        let diff = a.iter().zip(b).fold(0, |acc, (x, y)| acc | (x ^ y));
+        // Keep the optimizer from turning the fold into an early exit.
+        std::hint::black_box(diff) == 0
+    }
 }
 
 impl AsRef<[u8]> for HeaderValue {
@@ -440,6 +757,12 @@ macro_rules! from_integers {
             let n = $t::MAX;
             let val = HeaderValue::from(n);
             assert_eq!(val, &n.to_string());
+            assert!(val.len() <= $max_len);
+
+            let n = $t::MIN;
+            let val = HeaderValue::from(n);
+            assert_eq!(val, &n.to_string());
+            assert!(val.len() <= $max_len);
         }
     )*};
 }
@@ -453,7 +776,9 @@ from_integers! {
     from_u32: u32 => 10,
     from_i32: i32 => 11,
     from_u64: u64 => 20,
-    from_i64: i64 => 20
+    from_i64: i64 => 20,
+    from_u128: u128 => 39,
+    from_i128: i128 => 40
 }
 
 #[cfg(target_pointer_width = "16")]
@@ -576,15 +901,39 @@ mod try_from_header_name_tests {
     }
 }
 
-const fn is_visible_ascii(b: u8) -> bool {
+pub(super) const fn is_visible_ascii(b: u8) -> bool {
     b >= 32 && b < 127 || b == b'\t'
 }
 
 #[inline]
-const fn is_valid(b: u8) -> bool {
+pub(super) const fn is_valid(b: u8) -> bool {
     b >= 32 && b != 127 || b == b'\t'
 }
 
+#[inline]
+fn all_valid(bytes: &[u8]) -> bool {
+    #[cfg(feature = "simd")]
+    {
+        super::simd::all_valid(bytes)
+    }
+    #[cfg(not(feature = "simd"))]
+    {
+        bytes.iter().all(|&b| is_valid(b))
+    }
+}
+
+#[inline]
+fn all_visible_ascii(bytes: &[u8]) -> bool {
+    #[cfg(feature = "simd")]
+    {
+        super::simd::all_visible_ascii(bytes)
+    }
+    #[cfg(not(feature = "simd"))]
+    {
+        bytes.iter().all(|&b| is_visible_ascii(b))
+    }
+}
+
 impl fmt::Debug for InvalidHeaderValue {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("InvalidHeaderValue")
@@ -609,6 +958,247 @@ impl fmt::Display for ToStrError {
 
 impl Error for ToStrError {}
 
+impl fmt::Display for Base64DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("failed to decode header value as base64")
+    }
+}
+
+impl Error for Base64DecodeError {}
+
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+const fn base64_value(b: u8) -> Option<u8> {
+    match b {
+        b'A'..=b'Z' => Some(b - b'A'),
+        b'a'..=b'z' => Some(b - b'a' + 26),
+        b'0'..=b'9' => Some(b - b'0' + 52),
+        b'+' => Some(62),
+        b'/' => Some(63),
+        _ => None,
+    }
+}
+
+/// Splits `value` at every byte matching `is_separator`, ignoring
+/// separators inside quoted strings.
+pub fn split_unquoted<F>(value: &[u8], is_separator: F) -> impl Iterator<Item = &[u8]>
+where
+    F: Fn(u8) -> bool,
+{
+    let mut quoted = false;
+    let mut escaped = false;
+
+    value.split(move |&b| {
+        if escaped {
+            escaped = false;
+        } else if quoted && b == b'\\' {
+            escaped = true;
+        } else if b == b'"' {
+            quoted = !quoted;
+        } else if !quoted && is_separator(b) {
+            return true;
+        }
+        false
+    })
+}
+
+/// Parses a single, trimmed, element of a directive list.
+fn parse_directive(element: &[u8]) -> ParsedHeaderValue {
+    let Some(eq) = element.iter().position(|&b| b == b'=') else {
+        return ParsedHeaderValue::Token(String::from_utf8_lossy(element).into_owned());
+    };
+
+    ParsedHeaderValue::Pair(
+        String::from_utf8_lossy(element[..eq].trim_ascii()).into_owned(),
+        unquote(element[eq + 1..].trim_ascii()),
+    )
+}
+
+/// Removes the quotes and escapes from a quoted string, or returns `value`
+/// as is if it isn't quoted.
+fn unquote(value: &[u8]) -> String {
+    let Some(inner) = value
+        .strip_prefix(b"\"")
+        .and_then(|value| value.strip_suffix(b"\""))
+    else {
+        return String::from_utf8_lossy(value).into_owned();
+    };
+
+    let mut unescaped = Vec::with_capacity(inner.len());
+    let mut escaped = false;
+    for &b in inner {
+        if b == b'\\' && !escaped {
+            escaped = true;
+        } else {
+            unescaped.push(b);
+            escaped = false;
+        }
+    }
+    String::from_utf8_lossy(&unescaped).into_owned()
+}
+
+// ===== impl AsciiHeaderValue =====
+
+impl<'a> AsciiHeaderValue<'a> {
+    /// Returns the value as a string slice.
+    #[must_use]
+    pub const fn as_str(&self) -> &'a str {
+        self.s
+    }
+
+    /// Returns the underlying `HeaderValue`.
+    #[must_use]
+    pub const fn header_value(&self) -> &'a HeaderValue {
+        self.value
+    }
+}
+
+impl<'a> TryFrom<&'a HeaderValue> for AsciiHeaderValue<'a> {
+    type Error = ToStrError;
+
+    #[inline]
+    fn try_from(value: &'a HeaderValue) -> Result<Self, Self::Error> {
+        value.as_ascii()
+    }
+}
+
+impl std::ops::Deref for AsciiHeaderValue<'_> {
+    type Target = str;
+
+    #[inline]
+    fn deref(&self) -> &str {
+        self.s
+    }
+}
+
+impl AsRef<str> for AsciiHeaderValue<'_> {
+    #[inline]
+    fn as_ref(&self) -> &str {
+        self.s
+    }
+}
+
+impl AsRef<[u8]> for AsciiHeaderValue<'_> {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        self.s.as_bytes()
+    }
+}
+
+impl fmt::Debug for AsciiHeaderValue<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Keeps sensitive values hidden.
+        fmt::Debug::fmt(self.value, f)
+    }
+}
+
+impl fmt::Display for AsciiHeaderValue<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.s)
+    }
+}
+
+impl PartialEq<str> for AsciiHeaderValue<'_> {
+    #[inline]
+    fn eq(&self, other: &str) -> bool {
+        self.s == other
+    }
+}
+
+impl PartialEq<&str> for AsciiHeaderValue<'_> {
+    #[inline]
+    fn eq(&self, other: &&str) -> bool {
+        self.s == *other
+    }
+}
+
+// ===== impl HeaderValueBuilder =====
+
+impl HeaderValueBuilder {
+    /// Creates a new, empty `HeaderValueBuilder`.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::with_capacity(0)
+    }
+
+    /// Creates a new, empty `HeaderValueBuilder` with space for at least
+    /// `capacity` bytes.
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            buf: BytesMut::with_capacity(capacity),
+            is_valid: true,
+        }
+    }
+
+    /// Returns the number of bytes written so far.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.buf.len()
+    }
+
+    /// Returns true if nothing has been written yet.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.buf.is_empty()
+    }
+
+    /// Consumes the builder, returning the `HeaderValue` that was written.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any write contained bytes that are not valid in a
+    /// header value.
+    pub fn finish(self) -> Result<HeaderValue, InvalidHeaderValue> {
+        if !self.is_valid {
+            return Err(InvalidHeaderValue { _priv: () });
+        }
+
+        Ok(HeaderValue {
+            inner: self.buf.freeze(),
+            is_sensitive: false,
+        })
+    }
+
+    fn push(&mut self, bytes: &[u8]) -> Result<(), InvalidHeaderValue> {
+        if !all_valid(bytes) {
+            self.is_valid = false;
+        }
+
+        if !self.is_valid {
+            return Err(InvalidHeaderValue { _priv: () });
+        }
+
+        self.buf.extend_from_slice(bytes);
+        Ok(())
+    }
+}
+
+impl Default for HeaderValueBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Write for HeaderValueBuilder {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.push(s.as_bytes()).map_err(|_| fmt::Error)
+    }
+}
+
+impl std::io::Write for HeaderValueBuilder {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.push(buf)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
 // ===== PartialEq / PartialOrd =====
 
 impl Hash for HeaderValue {
@@ -795,3 +1385,197 @@ fn test_debug() {
     sensitive.set_sensitive(true);
     assert_eq!("Sensitive", format!("{sensitive:?}"));
 }
+
+#[test]
+fn test_builder() {
+    let mut builder = HeaderValueBuilder::new();
+    assert!(builder.is_empty());
+    write!(builder, "max-age={}", 60).unwrap();
+    std::io::Write::write_all(&mut builder, b", private").unwrap();
+    assert_eq!(builder.len(), 19);
+    assert_eq!(builder.finish().unwrap(), "max-age=60, private");
+
+    let mut builder = HeaderValueBuilder::with_capacity(8);
+    builder.write_str("ok").unwrap();
+    builder.write_str("bad\n").unwrap_err();
+    builder.write_str("ok").unwrap_err();
+    assert!(builder.finish().is_err());
+
+    let mut builder = HeaderValueBuilder::new();
+    std::io::Write::write_all(&mut builder, b"\x7f").unwrap_err();
+    assert!(HeaderValueBuilder::default().finish().unwrap().is_empty());
+    assert!(builder.finish().is_err());
+}
+
+#[test]
+fn test_constant_time_eq() {
+    let secret = HeaderValue::from_static("token-123");
+
+    assert!(secret.constant_time_eq(&HeaderValue::from_static("token-123")));
+    assert!(!secret.constant_time_eq(&HeaderValue::from_static("token-124")));
+    assert!(!secret.constant_time_eq(&HeaderValue::from_static("Token-123")));
+    assert!(!secret.constant_time_eq(&HeaderValue::from_static("token-1234")));
+    assert!(!secret.constant_time_eq(&HeaderValue::from_static("")));
+    assert!(HeaderValue::from_static("").constant_time_eq(&HeaderValue::from_static("")));
+}
+
+#[test]
+fn test_to_ascii_case() {
+    let value = HeaderValue::from_bytes(b"Gzip, CHUNKED; q=0.5 caf\xc3\x89").unwrap();
+    assert_eq!(
+        value.to_ascii_lowercase(),
+        &b"gzip, chunked; q=0.5 caf\xc3\x89"[..]
+    );
+    assert_eq!(
+        value.to_ascii_uppercase(),
+        &b"GZIP, CHUNKED; Q=0.5 CAF\xc3\x89"[..]
+    );
+
+    let lower = HeaderValue::from_static("chunked");
+    assert_eq!(
+        lower.to_ascii_lowercase().as_bytes().as_ptr(),
+        lower.as_bytes().as_ptr()
+    );
+
+    let mut secret = HeaderValue::from_static("Token");
+    secret.set_sensitive(true);
+    assert!(secret.to_ascii_lowercase().is_sensitive());
+    assert!(secret.to_ascii_uppercase().is_sensitive());
+}
+
+#[test]
+fn test_ordering() {
+    let a = HeaderValue::from_static("abc");
+    let b = HeaderValue::from_bytes(b"abd\xff").unwrap();
+    let abd = String::from("abd");
+
+    assert_eq!(a.partial_cmp(&b), Some(a.cmp(&b)));
+    assert!(a < b);
+    assert!(a < *"abd");
+    assert!(a > *"ab");
+    assert!(a < b"abd"[..]);
+    assert!(b > b"abd"[..]);
+    assert!(a < "abd");
+    assert!(a < abd);
+    assert!(*"ab" < a);
+    assert!(b"abd"[..] < b);
+    assert!(abd > a);
+    assert_eq!(a.partial_cmp("abc"), Some(cmp::Ordering::Equal));
+
+    // Byte order, so uppercase sorts before lowercase and obs-text last.
+    let mut values: Vec<_> = [&b"gzip"[..], b"\xff", b"Gzip", b"br", b""]
+        .iter()
+        .map(|s| HeaderValue::from_bytes(s).unwrap())
+        .collect();
+    values.sort();
+    assert_eq!(values, [&b""[..], b"Gzip", b"br", b"gzip", b"\xff"]);
+}
+
+#[test]
+fn test_as_ascii() {
+    let value = HeaderValue::from_static("text/html");
+    let ascii = value.as_ascii().unwrap();
+    assert_eq!(ascii, "text/html");
+    assert_eq!(ascii.as_str(), "text/html");
+    assert_eq!(AsRef::<str>::as_ref(&ascii), "text/html");
+    assert_eq!(AsRef::<[u8]>::as_ref(&ascii), b"text/html");
+    assert!(ascii.starts_with("text/"));
+    assert_eq!(ascii.to_string(), "text/html");
+    assert_eq!(ascii.header_value(), &value);
+
+    let mut secret = HeaderValue::from_static("token");
+    secret.set_sensitive(true);
+    assert_eq!(format!("{:?}", secret.as_ascii().unwrap()), "Sensitive");
+
+    for invalid in [&b"caf\xe9"[..], b"\x80"] {
+        let value = HeaderValue::from_bytes(invalid).unwrap();
+        assert!(value.as_ascii().is_err());
+        assert!(AsciiHeaderValue::try_from(&value).is_err());
+    }
+}
+
+#[test]
+fn test_parse_directives() {
+    use ParsedHeaderValue::{Pair, Token};
+
+    fn parse(value: &'static str) -> Vec<ParsedHeaderValue> {
+        HeaderValue::from_static(value).parse_directives().collect()
+    }
+
+    assert_eq!(
+        parse("public, max-age=3600"),
+        [
+            Token("public".into()),
+            Pair("max-age".into(), "3600".into())
+        ]
+    );
+    assert_eq!(
+        parse("text/html; charset=utf-8"),
+        [
+            Token("text/html".into()),
+            Pair("charset".into(), "utf-8".into())
+        ]
+    );
+    assert_eq!(
+        parse(r#"private="set-cookie, x-id" ,, no-store ; ext = "a\"b;c""#),
+        [
+            Pair("private".into(), "set-cookie, x-id".into()),
+            Token("no-store".into()),
+            Pair("ext".into(), r#"a"b;c"#.into()),
+        ]
+    );
+    assert_eq!(
+        parse("a=, =b"),
+        [
+            Pair("a".into(), String::new()),
+            Pair(String::new(), "b".into())
+        ]
+    );
+    assert_eq!(parse("q=\"x=y\""), [Pair("q".into(), "x=y".into())]);
+    assert!(parse("").is_empty());
+    assert!(parse(" ; , ").is_empty());
+
+    let value = HeaderValue::from_bytes(b"caf\xe9=1").unwrap();
+    assert_eq!(
+        value.parse_directives().collect::<Vec<_>>(),
+        [Pair("caf\u{fffd}".into(), "1".into())]
+    );
+}
+
+#[test]
+fn test_base64() {
+    // RFC 4648, section 10
+    let vectors: [(&[u8], &str); 7] = [
+        (b"", ""),
+        (b"f", "Zg=="),
+        (b"fo", "Zm8="),
+        (b"foo", "Zm9v"),
+        (b"foob", "Zm9vYg=="),
+        (b"fooba", "Zm9vYmE="),
+        (b"foobar", "Zm9vYmFy"),
+    ];
+    for (data, encoded) in vectors {
+        let value = HeaderValue::from_base64(data);
+        assert_eq!(value, encoded);
+        assert_eq!(value.to_base64_decoded().unwrap(), data);
+        let unpadded = HeaderValue::from_str(encoded.trim_end_matches('=')).unwrap();
+        assert_eq!(unpadded.to_base64_decoded().unwrap(), data);
+    }
+
+    let all: Vec<u8> = (0..=255).collect();
+    let value = HeaderValue::from_base64(&all);
+    assert!(
+        value
+            .as_bytes()
+            .iter()
+            .all(|&b| base64_value(b).is_some() || b == b'=')
+    );
+    assert_eq!(value.to_base64_decoded().unwrap(), all);
+
+    for invalid in [
+        "Z", "Zg=", "Zm9v=", "Zg==Zg==", "Zm9 v", "Zm9v-_", "Zh==", "Zm9=", "=",
+    ] {
+        let value = HeaderValue::from_str(invalid).unwrap();
+        assert!(value.to_base64_decoded().is_err(), "{invalid:?}");
+    }
+}
@@ -156,19 +156,26 @@
 #[macro_use]
 mod convert;
 
+pub mod connection;
+pub mod extensions;
 pub mod header;
 pub mod method;
 pub mod request;
 pub mod response;
+#[cfg(feature = "serde")]
+pub mod serde;
 pub mod status;
 pub mod uri;
 pub mod version;
 
 mod byte_str;
 mod error;
-mod extensions;
+mod macros;
 
-pub use crate::error::{Error, Result};
+#[doc(hidden)]
+pub use crate::macros::__private;
+
+pub use crate::error::{BuilderErrors, BuilderField, Error, ErrorKind, Result};
 pub use crate::extensions::Extensions;
 #[doc(no_inline)]
 pub use crate::header::{HeaderMap, HeaderName, HeaderValue};
@@ -0,0 +1,110 @@
+//! Macros for constants checked at compile time.
+
+/// Creates a [`Uri`](crate::Uri) from a string literal, checking it at
+/// compile time.
+///
+/// The literal is validated by the same parser as `Uri::from_static`, so an
+/// invalid URI fails the build with the parse error instead of panicking at
+/// runtime. The `Uri` itself is still built at runtime, without copying the
+/// string, since a `Uri` can't be a constant.
+///
+/// # Examples
+///
+/// ```
+/// let uri = http::uri!("https://example.com/health");
+/// assert_eq!(uri.host(), Some("example.com"));
+/// assert_eq!(uri.path(), "/health");
+/// ```
+///
+/// An invalid URI doesn't compile:
+///
+/// ```compile_fail
+/// let uri = http::uri!("https://example.com/with space");
+/// ```
+#[macro_export]
+macro_rules! uri {
+    ($uri:expr $(,)?) => {{
+        const _: () = $crate::__private::check_uri($uri);
+        $crate::Uri::from_static($uri)
+    }};
+}
+
+/// Creates a [`Method`](crate::Method) constant from a string literal,
+/// checking it at compile time.
+///
+/// Standard methods evaluate to the matching constant, such as
+/// `Method::GET`. Extension methods are limited to 15 bytes, since longer ones
+/// need an allocation; use `Method::from_bytes` for those.
+///
+/// # Examples
+///
+/// ```
+/// use http::Method;
+///
+/// const PURGE: Method = http::method!("PURGE");
+/// assert_eq!(PURGE.as_str(), "PURGE");
+/// assert_eq!(http::method!("GET"), Method::GET);
+/// ```
+///
+/// An invalid method doesn't compile:
+///
+/// ```compile_fail
+/// let method = http::method!("BAD METHOD");
+/// ```
+#[macro_export]
+macro_rules! method {
+    ($method:expr $(,)?) => {
+        const { $crate::__private::method($method) }
+    };
+}
+
+/// Creates a [`StatusCode`](crate::StatusCode) constant from an integer
+/// literal, checking at compile time that it is between 100 and 999.
+///
+/// # Examples
+///
+/// ```
+/// use http::StatusCode;
+///
+/// const SITE_FROZEN: StatusCode = http::status!(530);
+/// assert_eq!(SITE_FROZEN.as_u16(), 530);
+/// assert_eq!(http::status!(404), StatusCode::NOT_FOUND);
+/// ```
+///
+/// An out of range status code doesn't compile:
+///
+/// ```compile_fail
+/// let status = http::status!(1000);
+/// ```
+#[macro_export]
+macro_rules! status {
+    ($status:expr $(,)?) => {
+        const {
+            match $crate::StatusCode::from_u16($status) {
+                Ok(status) => status,
+                Err(_) => panic!(concat!(
+                    "invalid status code `",
+                    stringify!($status),
+                    "`: must be between 100 and 999",
+                )),
+            }
+        }
+    };
+}
+
+// Not public API; used by the macros above.
+#[doc(hidden)]
+pub mod __private {
+    use crate::Method;
+
+    pub const fn check_uri(src: &'static str) {
+        if let Err(err) = crate::uri::validate(src.as_bytes()) {
+            panic!("{}", err.description());
+        }
+    }
+
+    #[must_use]
+    pub const fn method(src: &'static str) -> Method {
+        Method::from_static(src)
+    }
+}
@@ -48,6 +48,7 @@ use std::{fmt, str};
 pub struct Method(Inner);
 
 /// A possible error value when converting `Method` from bytes.
+#[derive(Clone)]
 pub struct InvalidMethod {
     _priv: (),
 }
@@ -137,6 +138,64 @@ impl Method {
         }
     }
 
+    /// Converts a static string to a method in a const context, as used by
+    /// the `method!` macro.
+    ///
+    /// Extension methods must fit inline, since a const can't allocate.
+    pub(crate) const fn from_static(src: &'static str) -> Self {
+        match src.as_bytes() {
+            b"GET" => Self::GET,
+            b"PUT" => Self::PUT,
+            b"POST" => Self::POST,
+            b"HEAD" => Self::HEAD,
+            b"PATCH" => Self::PATCH,
+            b"TRACE" => Self::TRACE,
+            b"DELETE" => Self::DELETE,
+            b"OPTIONS" => Self::OPTIONS,
+            b"CONNECT" => Self::CONNECT,
+            b"" => panic!("invalid HTTP method: empty string"),
+            src if src.len() > InlineExtension::MAX => {
+                panic!(
+                    "invalid HTTP method: extension methods longer than 15 bytes need an allocation"
+                )
+            }
+            src => match InlineExtension::from_static(src) {
+                Some(inline) => Self(ExtensionInline(inline)),
+                None => panic!(
+                    "invalid HTTP method: contains a character that is not a token character"
+                ),
+            },
+        }
+    }
+
+    /// Converts a slice of bytes to an HTTP method, rejecting lowercase
+    /// letters.
+    ///
+    /// Methods are case-sensitive, so [`from_bytes`](Self::from_bytes)
+    /// accepts `get` as an extension method distinct from `GET`. Every
+    /// registered method is uppercase, so this stricter form is useful to
+    /// catch such mistakes, for example when reading methods from
+    /// configuration.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::Method;
+    /// assert_eq!(Method::from_bytes_strict(b"GET").unwrap(), Method::GET);
+    /// assert_eq!(Method::from_bytes_strict(b"PURGE").unwrap(), "PURGE");
+    /// assert!(Method::from_bytes_strict(b"get").is_err());
+    ///
+    /// // Still accepted, as an extension method.
+    /// assert_ne!(Method::from_bytes(b"get").unwrap(), Method::GET);
+    /// ```
+    pub fn from_bytes_strict(src: &[u8]) -> Result<Self, InvalidMethod> {
+        if src.iter().any(u8::is_ascii_lowercase) {
+            return Err(InvalidMethod::new());
+        }
+
+        Self::from_bytes(src)
+    }
+
     fn extension_inline(src: &[u8]) -> Result<Self, InvalidMethod> {
         let inline = InlineExtension::new(src)?;
 
@@ -166,6 +225,17 @@ impl Method {
         }
     }
 
+    /// Whether a method is one of the standard methods that modify data on
+    /// the server: `POST`, `PUT`, `DELETE` or `PATCH`.
+    ///
+    /// This is narrower than `!is_safe()`, since `CONNECT` is not safe but
+    /// does not modify data either. Extension methods are never considered
+    /// writes.
+    #[must_use]
+    pub const fn is_write(&self) -> bool {
+        matches!(self.0, Post | Put | Delete | Patch)
+    }
+
     /// Return a &str representation of the HTTP method
     #[inline]
     #[must_use]
@@ -193,6 +263,13 @@ impl AsRef<str> for Method {
     }
 }
 
+impl AsRef<[u8]> for Method {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        self.as_str().as_bytes()
+    }
+}
+
 impl<'a> PartialEq<&'a Self> for Method {
     #[inline]
     fn eq(&self, other: &&'a Self) -> bool {
@@ -210,28 +287,28 @@ impl PartialEq<Method> for &Method {
 impl PartialEq<str> for Method {
     #[inline]
     fn eq(&self, other: &str) -> bool {
-        self.as_ref() == other
+        self.as_str() == other
     }
 }
 
 impl PartialEq<Method> for str {
     #[inline]
     fn eq(&self, other: &Method) -> bool {
-        self == other.as_ref()
+        self == other.as_str()
     }
 }
 
 impl<'a> PartialEq<&'a str> for Method {
     #[inline]
     fn eq(&self, other: &&'a str) -> bool {
-        self.as_ref() == *other
+        self.as_str() == *other
     }
 }
 
 impl PartialEq<Method> for &str {
     #[inline]
     fn eq(&self, other: &Method) -> bool {
-        *self == other.as_ref()
+        *self == other.as_str()
     }
 }
 
@@ -316,7 +393,7 @@ mod extension {
 
     #[derive(Clone, PartialEq, Eq, Hash)]
     // Invariant: the first self.1 bytes of self.0 are valid UTF-8.
-    pub struct InlineExtension([u8; InlineExtension::MAX], u8);
+    pub struct InlineExtension([u8; Self::MAX], u8);
 
     #[derive(Clone, PartialEq, Eq, Hash)]
     // Invariant: self.0 contains valid UTF-8.
@@ -336,6 +413,28 @@ mod extension {
             Ok(Self(data, src.len() as u8))
         }
 
+        // Like `new`, but usable in const contexts. `src` must be at most
+        // `MAX` bytes long.
+        pub const fn from_static(src: &[u8]) -> Option<Self> {
+            let mut data = [0; Self::MAX];
+
+            let mut i = 0;
+            while i < src.len() {
+                let b = METHOD_CHARS[src[i] as usize];
+
+                if b == 0 {
+                    return None;
+                }
+
+                data[i] = b;
+                i += 1;
+            }
+
+            // Invariant: every byte was checked against METHOD_CHARS, so the
+            // first src.len() bytes of data are valid UTF-8.
+            Some(Self(data, src.len() as u8))
+        }
+
         pub fn as_str(&self) -> &str {
             let Self(data, len) = self;
             // Safety: the invariant of InlineExtension ensures that the first
@@ -464,6 +563,21 @@ mod test {
         assert!(!Method::PATCH.is_idempotent());
     }
 
+    #[test]
+    fn test_is_write() {
+        assert!(Method::POST.is_write());
+        assert!(Method::PUT.is_write());
+        assert!(Method::DELETE.is_write());
+        assert!(Method::PATCH.is_write());
+
+        assert!(!Method::GET.is_write());
+        assert!(!Method::HEAD.is_write());
+        assert!(!Method::OPTIONS.is_write());
+        assert!(!Method::TRACE.is_write());
+        assert!(!Method::CONNECT.is_write());
+        assert!(!Method::from_str("PURGE").unwrap().is_write());
+    }
+
     #[test]
     fn test_extension_method() {
         assert_eq!(Method::from_str("WOW").unwrap(), "WOW");
@@ -488,6 +602,58 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_from_static() {
+        for src in [
+            "GET",
+            "PUT",
+            "POST",
+            "HEAD",
+            "PATCH",
+            "TRACE",
+            "DELETE",
+            "OPTIONS",
+            "CONNECT",
+            "PURGE",
+            "get",
+            "M-SEARCH",
+            "A",
+            "FIFTEEN-CHARSXX",
+        ] {
+            let method = Method::from_static(src);
+            assert_eq!(method, Method::from_bytes(src.as_bytes()).unwrap());
+            assert_eq!(method.as_str(), src);
+        }
+    }
+
+    #[test]
+    fn test_from_bytes_strict() {
+        for method in [
+            Method::GET,
+            Method::POST,
+            Method::PUT,
+            Method::DELETE,
+            Method::HEAD,
+            Method::OPTIONS,
+            Method::CONNECT,
+            Method::PATCH,
+            Method::TRACE,
+        ] {
+            let bytes = method.as_str().as_bytes();
+            assert_eq!(Method::from_bytes_strict(bytes).unwrap(), method);
+            assert!(Method::from_bytes_strict(&bytes.to_ascii_lowercase()).is_err());
+        }
+
+        assert_eq!(Method::from_bytes_strict(b"M-SEARCH").unwrap(), "M-SEARCH");
+        let long = [b'X'; InlineExtension::MAX + 1];
+        assert!(Method::from_bytes_strict(&long).is_ok());
+
+        assert!(Method::from_bytes_strict(b"Get").is_err());
+        assert!(Method::from_bytes_strict(b"wOw!!").is_err());
+        assert!(Method::from_bytes_strict(b"").is_err());
+        assert!(Method::from_bytes_strict(b"G T").is_err());
+    }
+
     #[test]
     fn test_extension_method_chars() {
         const VALID_METHOD_CHARS: &str =
@@ -503,4 +669,16 @@ mod test {
             );
         }
     }
+
+    #[test]
+    fn test_as_ref_bytes() {
+        fn bytes<T: AsRef<[u8]>>(t: &T) -> &[u8] {
+            t.as_ref()
+        }
+
+        assert_eq!(bytes(&Method::GET), b"GET");
+        assert_eq!(bytes(&Method::from_bytes(b"PURGE").unwrap()), b"PURGE");
+        let long = "A".repeat(InlineExtension::MAX + 1);
+        assert_eq!(bytes(&Method::from_str(&long).unwrap()), long.as_bytes());
+    }
 }
@@ -62,13 +62,19 @@
 //! ```
 
 use std::any::Any;
-use std::convert::TryInto;
+use std::convert::{TryFrom, TryInto};
+use std::error::Error;
 use std::fmt;
 
-use crate::header::{HeaderMap, HeaderName, HeaderValue};
+use bytes::Bytes;
+
+use crate::header::{
+    CACHE_CONTROL, CONTENT_LENGTH, CONTENT_TYPE, HeaderMap, HeaderName, HeaderValue, LOCATION,
+    Trailers, is_connection_specific,
+};
 use crate::status::StatusCode;
 use crate::version::Version;
-use crate::{Extensions, Result};
+use crate::{BuilderErrors, BuilderField, Extensions, Result, Uri};
 
 /// Represents an HTTP response
 ///
@@ -190,6 +196,9 @@ pub struct Response<T> {
 #[non_exhaustive]
 pub struct Parts {
     /// The response's status
+    ///
+    /// Assigning this keeps any custom reason phrase. Use
+    /// [`set_status`](Self::set_status) to drop it as well.
     pub status: StatusCode,
 
     /// The response's version
@@ -200,6 +209,32 @@ pub struct Parts {
 
     /// The response's extensions
     pub extensions: Extensions,
+
+    trailers: Option<Box<HeaderMap<HeaderValue>>>,
+
+    reason: Option<ReasonPhrase>,
+}
+
+/// A reason phrase to send with a response status code.
+///
+/// HTTP/1 responses carry a textual reason phrase after the status code, such
+/// as `Not Found` in `HTTP/1.1 404 Not Found`. It is usually the canonical
+/// reason for the status code, but a `ReasonPhrase` can be stored on a
+/// response to send (or forward) a different one.
+///
+/// A reason phrase may contain horizontal tabs, spaces, visible ASCII
+/// characters and opaque octets (128-255). Other control characters,
+/// including CR and LF, are rejected.
+///
+/// HTTP/2 and later have no reason phrase, so it is dropped when a response
+/// is sent using those versions.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct ReasonPhrase(Bytes);
+
+/// A possible error when converting a `ReasonPhrase` from another type.
+#[derive(Clone)]
+pub struct InvalidReasonPhrase {
+    _priv: (),
 }
 
 /// An HTTP response builder
@@ -209,6 +244,18 @@ pub struct Parts {
 #[derive(Debug)]
 pub struct Builder {
     inner: Result<Parts>,
+    errors: Option<BuilderErrors>,
+    strict_version_headers: bool,
+}
+
+/// The `:status` pseudo-header field of an HTTP/2 or HTTP/3 response,
+/// followed by its regular header fields.
+///
+/// This is created by [`Parts::to_pseudo_headers`].
+#[derive(Debug)]
+pub struct PseudoHeaders<'a> {
+    status: &'a StatusCode,
+    headers: &'a HeaderMap<HeaderValue>,
 }
 
 impl Response<()> {
@@ -293,6 +340,9 @@ impl<T> Response<T> {
 
     /// Returns a mutable reference to the associated `StatusCode`.
     ///
+    /// Any custom reason phrase is kept, even though it was chosen for the
+    /// old status. Use [`set_status`](Self::set_status) to drop it as well.
+    ///
     /// # Examples
     ///
     /// ```
@@ -306,6 +356,42 @@ impl<T> Response<T> {
         &mut self.head.status
     }
 
+    /// Sets the `StatusCode`, clearing any custom reason phrase.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::*;
+    /// let mut response = Response::builder()
+    ///     .reason("Fine")
+    ///     .body(())
+    ///     .unwrap();
+    ///
+    /// response.set_status(StatusCode::NOT_FOUND);
+    /// assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    /// assert!(response.reason().is_none());
+    /// ```
+    #[inline]
+    pub fn set_status(&mut self, status: StatusCode) {
+        self.head.set_status(status);
+    }
+
+    /// Returns the custom reason phrase, if any.
+    ///
+    /// `None` means the canonical reason for the status code should be used.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::*;
+    /// let response: Response<()> = Response::default();
+    /// assert!(response.reason().is_none());
+    /// ```
+    #[inline]
+    pub const fn reason(&self) -> Option<&ReasonPhrase> {
+        self.head.reason()
+    }
+
     /// Returns a reference to the associated version.
     ///
     /// # Examples
@@ -395,6 +481,56 @@ impl<T> Response<T> {
         &mut self.head.extensions
     }
 
+    /// Returns a reference to the associated trailers, if any were set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::*;
+    /// let response: Response<()> = Response::default();
+    /// assert!(response.trailers().is_none());
+    /// ```
+    #[inline]
+    pub fn trailers(&self) -> Option<&HeaderMap<HeaderValue>> {
+        self.head.trailers()
+    }
+
+    /// Returns a mutable reference to the associated trailers, allocating
+    /// them on first use.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::*;
+    /// let mut response: Response<()> = Response::default();
+    /// response.trailers_mut().insert("grpc-status", HeaderValue::from_static("0"));
+    /// assert!(response.trailers().is_some());
+    /// ```
+    #[inline]
+    pub fn trailers_mut(&mut self) -> &mut HeaderMap<HeaderValue> {
+        self.head.trailers_mut()
+    }
+
+    /// Replaces the trailers of this response, returning the previous
+    /// trailers if any were set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::*;
+    /// # use http::header::Trailers;
+    /// let mut trailers = Trailers::new();
+    /// trailers.insert("grpc-status", HeaderValue::from_static("0"));
+    ///
+    /// let mut response: Response<()> = Response::default();
+    /// assert!(response.set_trailers(trailers).is_none());
+    /// assert_eq!(response.trailers().unwrap()["grpc-status"], "0");
+    /// ```
+    #[inline]
+    pub fn set_trailers(&mut self, trailers: Trailers) -> Option<Trailers> {
+        self.head.set_trailers(trailers)
+    }
+
     /// Returns a reference to the associated HTTP body.
     ///
     /// # Examples
@@ -454,6 +590,30 @@ impl<T> Response<T> {
         (self.head, self.body)
     }
 
+    /// Consumes the response returning the head, body and trailers.
+    ///
+    /// The trailers are moved out of the returned `Parts`, so a proxy can
+    /// forward the head right away and send the trailers after the body.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::*;
+    /// let response = Response::builder()
+    ///     .trailer("grpc-status", "0")
+    ///     .body(())
+    ///     .unwrap();
+    ///
+    /// let (parts, _body, trailers) = response.into_parts_with_trailers();
+    /// assert!(parts.trailers().is_none());
+    /// assert_eq!(trailers.unwrap()["grpc-status"], "0");
+    /// ```
+    #[inline]
+    pub fn into_parts_with_trailers(mut self) -> (Parts, T, Option<Trailers>) {
+        let trailers = self.head.take_trailers();
+        (self.head, self.body, trailers)
+    }
+
     /// Consumes the response returning a new response with body mapped to the
     /// return type of the passed in function.
     ///
@@ -478,6 +638,47 @@ impl<T> Response<T> {
             head: self.head,
         }
     }
+
+    /// Consumes the response returning a new response with the body converted
+    /// into `U` using its `From` implementation.
+    ///
+    /// This is shorthand for `response.map(Into::into)`; every part of the
+    /// response, including its extensions, is kept.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::*;
+    /// # use bytes::Bytes;
+    /// let response = Response::new(b"hello".to_vec());
+    /// let response: Response<Bytes> = response.into_body_type();
+    /// assert_eq!(response.body(), &Bytes::from_static(b"hello"));
+    /// ```
+    #[inline]
+    pub fn into_body_type<U>(self) -> Response<U>
+    where
+        U: From<T>,
+    {
+        self.map(U::from)
+    }
+
+    /// Returns an estimate, in bytes, of the serialized size of this response
+    /// head.
+    ///
+    /// See [`Parts::head_size`] for how the estimate is made.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::*;
+    /// let response = Response::builder().header("Content-Length", 0).body(()).unwrap();
+    /// assert_eq!(response.head_size(4), "HTTP/1.1 200 OK\r\ncontent-length: 0\r\n\r\n".len());
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn head_size(&self, entry_overhead: usize) -> usize {
+        self.head.head_size(entry_overhead)
+    }
 }
 
 impl<T: Default> Default for Response<T> {
@@ -487,6 +688,25 @@ impl<T: Default> Default for Response<T> {
     }
 }
 
+impl From<StatusCode> for Response<()> {
+    #[inline]
+    fn from(status: StatusCode) -> Self {
+        let mut response = Self::new(());
+        response.head.status = status;
+        response
+    }
+}
+
+impl From<(StatusCode, HeaderMap<HeaderValue>)> for Response<()> {
+    #[inline]
+    fn from((status, headers): (StatusCode, HeaderMap<HeaderValue>)) -> Self {
+        let mut response = Self::new(());
+        response.head.status = status;
+        response.head.headers = headers;
+        response
+    }
+}
+
 impl<T: fmt::Debug> fmt::Debug for Response<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("Response")
@@ -499,6 +719,23 @@ impl<T: fmt::Debug> fmt::Debug for Response<T> {
     }
 }
 
+/// Compares the status, reason phrase, version, headers, trailers and body
+/// of two `Response`s.
+///
+/// Extensions are ignored, since extension values are of arbitrary types.
+/// Headers are compared with `HeaderMap`'s `PartialEq`, so the order of
+/// distinct header names is ignored but the order of values for the same
+/// name is not.
+#[cfg(feature = "test-util")]
+impl<T: PartialEq> PartialEq for Response<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.head == other.head && self.body == other.body
+    }
+}
+
+#[cfg(feature = "test-util")]
+impl<T: Eq> Eq for Response<T> {}
+
 impl Parts {
     /// Creates a new default instance of `Parts`
     const fn new() -> Self {
@@ -507,6 +744,217 @@ impl Parts {
             version: Version::new(),
             headers: HeaderMap::new(),
             extensions: Extensions::new(),
+            trailers: None,
+            reason: None,
+        }
+    }
+
+    /// Sets the status of this response, clearing any custom reason phrase.
+    ///
+    /// A reason phrase describes the status it was set with, so it is
+    /// dropped rather than sent with a different status.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::*;
+    /// let response = Response::builder()
+    ///     .reason("Fine")
+    ///     .body(())
+    ///     .unwrap();
+    /// let (mut parts, _) = response.into_parts();
+    ///
+    /// parts.set_status(StatusCode::NOT_FOUND);
+    /// assert_eq!(parts.status, StatusCode::NOT_FOUND);
+    /// assert!(parts.reason().is_none());
+    /// ```
+    #[inline]
+    pub fn set_status(&mut self, status: StatusCode) {
+        self.status = status;
+        self.reason = None;
+    }
+
+    /// Returns the custom reason phrase of this response, if any.
+    ///
+    /// `None` means the canonical reason for the status code should be used.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::*;
+    /// let response = Response::builder()
+    ///     .status(404)
+    ///     .reason("Not Found Anywhere")
+    ///     .body(())
+    ///     .unwrap();
+    /// let (parts, _) = response.into_parts();
+    ///
+    /// assert_eq!(parts.reason().unwrap().as_bytes(), b"Not Found Anywhere");
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn reason(&self) -> Option<&ReasonPhrase> {
+        self.reason.as_ref()
+    }
+
+    /// Sets the custom reason phrase of this response.
+    ///
+    /// Setting `None` restores the canonical reason for the status code.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::*;
+    /// # use http::response::ReasonPhrase;
+    /// let (mut parts, body) = Response::new(()).into_parts();
+    /// parts.set_reason(Some(ReasonPhrase::from_static("Fine")));
+    ///
+    /// let response = Response::from_parts(parts, body);
+    /// assert_eq!(response.reason().unwrap().as_bytes(), b"Fine");
+    /// ```
+    #[inline]
+    pub fn set_reason(&mut self, reason: Option<ReasonPhrase>) {
+        self.reason = reason;
+    }
+
+    /// Returns the trailers of this response, if any were set.
+    ///
+    /// Trailers are header fields sent after the body, such as HTTP/1.1
+    /// chunked trailers or HTTP/2 and HTTP/3 trailing HEADERS frames.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::*;
+    /// let (parts, _) = Response::new(()).into_parts();
+    /// assert!(parts.trailers().is_none());
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn trailers(&self) -> Option<&HeaderMap<HeaderValue>> {
+        self.trailers.as_deref()
+    }
+
+    /// Returns a mutable reference to the trailers of this response.
+    ///
+    /// The trailers are allocated on first use, so a response without trailers
+    /// does not pay for them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::*;
+    /// let (mut parts, _) = Response::new(()).into_parts();
+    /// parts
+    ///     .trailers_mut()
+    ///     .insert("grpc-status", HeaderValue::from_static("0"));
+    /// assert_eq!(parts.trailers().unwrap()["grpc-status"], "0");
+    /// ```
+    #[inline]
+    pub fn trailers_mut(&mut self) -> &mut HeaderMap<HeaderValue> {
+        self.trailers.get_or_insert_with(Box::default)
+    }
+
+    /// Replaces the trailers of this response, returning the previous
+    /// trailers if any were set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::*;
+    /// # use http::header::Trailers;
+    /// let (mut parts, _) = Response::new(()).into_parts();
+    /// parts.set_trailers(Trailers::new());
+    ///
+    /// let previous = parts.set_trailers(Trailers::new());
+    /// assert!(previous.unwrap().is_empty());
+    /// ```
+    #[inline]
+    pub fn set_trailers(&mut self, trailers: Trailers) -> Option<Trailers> {
+        self.trailers
+            .replace(Box::new(trailers))
+            .map(|trailers| *trailers)
+    }
+
+    /// Removes the trailers of this response, returning them if any were set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::*;
+    /// let (mut parts, _) = Response::new(()).into_parts();
+    /// parts
+    ///     .trailers_mut()
+    ///     .insert("grpc-status", HeaderValue::from_static("0"));
+    ///
+    /// let trailers = parts.take_trailers().unwrap();
+    /// assert_eq!(trailers["grpc-status"], "0");
+    /// assert!(parts.trailers().is_none());
+    /// ```
+    #[inline]
+    pub fn take_trailers(&mut self) -> Option<HeaderMap<HeaderValue>> {
+        self.trailers.take().map(|trailers| *trailers)
+    }
+
+    /// Returns an estimate, in bytes, of the serialized size of this response
+    /// head.
+    ///
+    /// The estimate is the length of an HTTP/1.1 style status line
+    /// (`{version} {status} {reason}\r\n`), plus the length of every header
+    /// name and value with `entry_overhead` bytes added per header field, plus
+    /// the final empty line. The custom reason phrase is used if set,
+    /// otherwise the canonical reason for the status code.
+    ///
+    /// Pass an `entry_overhead` of `4` to account for the `": "` and `"\r\n"`
+    /// surrounding each HTTP/1.1 header field, or `32` to match the per-field
+    /// overhead used for HTTP/2 `SETTINGS_MAX_HEADER_LIST_SIZE` (RFC 9113,
+    /// Section 6.5.2).
+    ///
+    /// This function does not allocate.
+    #[must_use]
+    pub fn head_size(&self, entry_overhead: usize) -> usize {
+        self.version.as_str().len()
+            + 1
+            + 3
+            + 1
+            + self.reason.as_ref().map_or_else(
+                || self.status.canonical_reason().map_or(0, str::len),
+                ReasonPhrase::len,
+            )
+            + 2
+            + self.headers.encoded_len(entry_overhead)
+            + 2
+    }
+
+    /// Maps the response head to HTTP/2 or HTTP/3 pseudo-header fields
+    /// (RFC 9113, Section 8.3.2).
+    ///
+    /// Connection-specific headers are left out of
+    /// [`PseudoHeaders::headers`], and any custom reason phrase is dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::*;
+    /// let response = Response::builder()
+    ///     .status(StatusCode::NOT_FOUND)
+    ///     .header("content-type", "text/plain")
+    ///     .header("transfer-encoding", "chunked")
+    ///     .body(())
+    ///     .unwrap();
+    ///
+    /// let (parts, _) = response.into_parts();
+    /// let pseudo = parts.to_pseudo_headers();
+    /// assert_eq!(pseudo.status(), b"404");
+    ///
+    /// let headers: Vec<_> = pseudo.headers().map(|(name, _)| name.as_str()).collect();
+    /// assert_eq!(headers, ["content-type"]);
+    /// ```
+    #[must_use]
+    pub const fn to_pseudo_headers(&self) -> PseudoHeaders<'_> {
+        PseudoHeaders {
+            status: &self.status,
+            headers: &self.headers,
         }
     }
 }
@@ -522,6 +970,24 @@ impl fmt::Debug for Parts {
     }
 }
 
+/// Compares the status, reason phrase, version, headers and trailers of two
+/// `Parts`.
+///
+/// Extensions are ignored, since extension values are of arbitrary types.
+#[cfg(feature = "test-util")]
+impl PartialEq for Parts {
+    fn eq(&self, other: &Self) -> bool {
+        self.status == other.status
+            && self.reason == other.reason
+            && self.version == other.version
+            && self.headers == other.headers
+            && self.trailers == other.trailers
+    }
+}
+
+#[cfg(feature = "test-util")]
+impl Eq for Parts {}
+
 impl Builder {
     /// Creates a new default instance of `Builder` to construct either a
     /// `Head` or a `Response`.
@@ -541,6 +1007,8 @@ impl Builder {
     pub const fn new() -> Self {
         Self {
             inner: Ok(Parts::new()),
+            errors: None,
+            strict_version_headers: false,
         }
     }
 
@@ -559,74 +1027,304 @@ impl Builder {
     ///     .unwrap();
     /// ```
     #[must_use]
+    #[cfg_attr(feature = "debug-location", track_caller)]
     pub fn status<T>(self, status: T) -> Self
     where
         T: TryInto<StatusCode>,
         <T as TryInto<StatusCode>>::Error: Into<crate::Error>,
     {
-        self.and_then(move |mut head| {
+        self.and_then(BuilderField::Status, move |head| {
             head.status = status.try_into().map_err(Into::into)?;
-            Ok(head)
+            Ok(())
         })
     }
 
-    /// Set the HTTP version for this response.
+    /// Get the HTTP status for this response.
     ///
-    /// By default this is HTTP/1.1
+    /// By default this is `200`. If builder has error, returns None.
     ///
     /// # Examples
     ///
     /// ```
     /// # use http::*;
     ///
-    /// let response = Response::builder()
-    ///     .version(Version::HTTP_2)
-    ///     .body(())
-    ///     .unwrap();
+    /// let mut res = Response::builder();
+    /// assert_eq!(res.status_ref(), Some(&StatusCode::OK));
+    ///
+    /// res = res.status(404);
+    /// assert_eq!(res.status_ref(), Some(&StatusCode::NOT_FOUND));
     /// ```
-    #[must_use]
-    pub fn version(self, version: Version) -> Self {
-        self.and_then(move |mut head| {
-            head.version = version;
-            Ok(head)
-        })
+    pub fn status_ref(&self) -> Option<&StatusCode> {
+        self.head_ref().map(|h| &h.status)
     }
 
-    /// Appends a header to this response builder.
+    /// Get a mutable reference to the HTTP status for this response.
     ///
-    /// This function will append the provided key/value as a header to the
-    /// internal `HeaderMap` being constructed. Essentially this is equivalent
-    /// to calling `HeaderMap::append`.
+    /// By default this is `200`. If builder has error, returns None.
     ///
     /// # Examples
     ///
     /// ```
     /// # use http::*;
-    /// # use http::header::HeaderValue;
     ///
-    /// let response = Response::builder()
-    ///     .header("Content-Type", "text/html")
-    ///     .header("X-Custom-Foo", "bar")
-    ///     .header("content-length", 0)
-    ///     .body(())
-    ///     .unwrap();
+    /// let mut res = Response::builder();
+    /// *res.status_mut().unwrap() = StatusCode::CREATED;
+    /// assert_eq!(res.status_ref(), Some(&StatusCode::CREATED));
     /// ```
-    #[must_use]
-    pub fn header<K, V>(self, key: K, value: V) -> Self
-    where
-        K: TryInto<HeaderName>,
+    pub fn status_mut(&mut self) -> Option<&mut StatusCode> {
+        self.head_mut().map(|h| &mut h.status)
+    }
+
+    /// Set a custom reason phrase for this response.
+    ///
+    /// By default the canonical reason for the status code is used.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::*;
+    ///
+    /// let response = Response::builder()
+    ///     .status(404)
+    ///     .reason("Not Found Anywhere")
+    ///     .body(())
+    ///     .unwrap();
+    ///
+    /// assert!(Response::builder().reason("Bad\r\nReason").body(()).is_err());
+    /// ```
+    #[must_use]
+    #[cfg_attr(feature = "debug-location", track_caller)]
+    pub fn reason<T>(self, reason: T) -> Self
+    where
+        T: TryInto<ReasonPhrase>,
+        <T as TryInto<ReasonPhrase>>::Error: Into<crate::Error>,
+    {
+        self.and_then(BuilderField::Reason, move |head| {
+            head.reason = Some(reason.try_into().map_err(Into::into)?);
+            Ok(())
+        })
+    }
+
+    /// Set the HTTP version for this response.
+    ///
+    /// By default this is HTTP/1.1
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::*;
+    ///
+    /// let response = Response::builder()
+    ///     .version(Version::HTTP_2)
+    ///     .body(())
+    ///     .unwrap();
+    /// ```
+    #[must_use]
+    pub fn version(self, version: Version) -> Self {
+        self.map(move |head| {
+            head.version = version;
+        })
+    }
+
+    /// Get the HTTP version for this response
+    ///
+    /// By default this is HTTP/1.1. If builder has error, returns None.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::*;
+    ///
+    /// let mut res = Response::builder();
+    /// assert_eq!(res.version_ref(), Some(&Version::HTTP_11));
+    ///
+    /// res = res.version(Version::HTTP_2);
+    /// assert_eq!(res.version_ref(), Some(&Version::HTTP_2));
+    /// ```
+    pub fn version_ref(&self) -> Option<&Version> {
+        self.head_ref().map(|h| &h.version)
+    }
+
+    /// Get a mutable reference to the HTTP version for this response
+    ///
+    /// By default this is HTTP/1.1. If builder has error, returns None.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::*;
+    ///
+    /// let mut res = Response::builder();
+    /// *res.version_mut().unwrap() = Version::HTTP_3;
+    /// assert_eq!(res.version_ref(), Some(&Version::HTTP_3));
+    /// ```
+    pub fn version_mut(&mut self) -> Option<&mut Version> {
+        self.head_mut().map(|h| &mut h.version)
+    }
+
+    /// Appends a header to this response builder.
+    ///
+    /// This function will append the provided key/value as a header to the
+    /// internal `HeaderMap` being constructed. Essentially this is equivalent
+    /// to calling `HeaderMap::append`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::*;
+    /// # use http::header::HeaderValue;
+    ///
+    /// let response = Response::builder()
+    ///     .header("Content-Type", "text/html")
+    ///     .header("X-Custom-Foo", "bar")
+    ///     .header("content-length", 0)
+    ///     .body(())
+    ///     .unwrap();
+    /// ```
+    #[must_use]
+    #[cfg_attr(feature = "debug-location", track_caller)]
+    pub fn header<K, V>(self, key: K, value: V) -> Self
+    where
+        K: TryInto<HeaderName>,
         <K as TryInto<HeaderName>>::Error: Into<crate::Error>,
         V: TryInto<HeaderValue>,
         <V as TryInto<HeaderValue>>::Error: Into<crate::Error>,
     {
-        self.and_then(move |mut head| {
+        self.and_then(BuilderField::Header, move |head| {
             let name = key.try_into().map_err(Into::into)?;
             let value = value.try_into().map_err(Into::into)?;
             head.headers.try_append(name, value)?;
-            Ok(head)
+            Ok(())
         })
     }
 
+    /// Appends a trailer to this response builder.
+    ///
+    /// This function will append the provided key/value to the trailers of
+    /// the response, allocating them on first use. Essentially this is
+    /// equivalent to calling `HeaderMap::append` on the trailers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::*;
+    ///
+    /// let response = Response::builder()
+    ///     .header("Trailer", "grpc-status")
+    ///     .trailer("grpc-status", "0")
+    ///     .body(())
+    ///     .unwrap();
+    ///
+    /// assert_eq!(response.trailers().unwrap()["grpc-status"], "0");
+    /// ```
+    #[must_use]
+    #[cfg_attr(feature = "debug-location", track_caller)]
+    pub fn trailer<K, V>(self, key: K, value: V) -> Self
+    where
+        K: TryInto<HeaderName>,
+        <K as TryInto<HeaderName>>::Error: Into<crate::Error>,
+        V: TryInto<HeaderValue>,
+        <V as TryInto<HeaderValue>>::Error: Into<crate::Error>,
+    {
+        self.and_then(BuilderField::Trailer, move |head| {
+            let name = key.try_into().map_err(Into::into)?;
+            let value = value.try_into().map_err(Into::into)?;
+            head.trailers_mut().try_append(name, value)?;
+            Ok(())
+        })
+    }
+
+    /// Sets the `Content-Type` header of this response.
+    ///
+    /// This is equivalent to calling `header(CONTENT_TYPE, content_type)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::*;
+    /// let body = r#"{"hello":"world"}"#;
+    /// let response = Response::builder()
+    ///     .content_type("application/json")
+    ///     .content_length(body.len() as u64)
+    ///     .cache_control("no-store")
+    ///     .body(body)
+    ///     .unwrap();
+    ///
+    /// assert_eq!(response.headers()[header::CONTENT_TYPE], "application/json");
+    /// assert_eq!(response.headers()[header::CONTENT_LENGTH], "17");
+    /// assert_eq!(response.headers()[header::CACHE_CONTROL], "no-store");
+    /// ```
+    #[must_use]
+    #[cfg_attr(feature = "debug-location", track_caller)]
+    pub fn content_type(self, content_type: &str) -> Self {
+        self.header(CONTENT_TYPE, content_type)
+    }
+
+    /// Sets the `Content-Length` header of this response.
+    ///
+    /// This is equivalent to calling `header(CONTENT_LENGTH, content_length)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::*;
+    /// let response = Response::builder()
+    ///     .content_length(0)
+    ///     .body(())
+    ///     .unwrap();
+    ///
+    /// assert_eq!(response.headers()[header::CONTENT_LENGTH], "0");
+    /// ```
+    #[must_use]
+    #[cfg_attr(feature = "debug-location", track_caller)]
+    pub fn content_length(self, content_length: u64) -> Self {
+        self.header(CONTENT_LENGTH, content_length)
+    }
+
+    /// Sets the `Cache-Control` header of this response.
+    ///
+    /// This is equivalent to calling `header(CACHE_CONTROL, cache_control)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::*;
+    /// let response = Response::builder()
+    ///     .cache_control("max-age=60")
+    ///     .body(())
+    ///     .unwrap();
+    ///
+    /// assert_eq!(response.headers()[header::CACHE_CONTROL], "max-age=60");
+    /// ```
+    #[must_use]
+    #[cfg_attr(feature = "debug-location", track_caller)]
+    pub fn cache_control(self, cache_control: &str) -> Self {
+        self.header(CACHE_CONTROL, cache_control)
+    }
+
+    /// Sets the `Location` header of this response.
+    ///
+    /// This is equivalent to calling `header(LOCATION, location.to_string())`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::*;
+    /// let location = Uri::from_static("https://www.rust-lang.org/");
+    /// let response = Response::builder()
+    ///     .status(StatusCode::FOUND)
+    ///     .location(&location)
+    ///     .body(())
+    ///     .unwrap();
+    ///
+    /// assert_eq!(response.headers()[header::LOCATION], "https://www.rust-lang.org/");
+    /// ```
+    #[must_use]
+    #[cfg_attr(feature = "debug-location", track_caller)]
+    pub fn location(self, location: &Uri) -> Self {
+        self.header(LOCATION, location.to_string())
+    }
+
     /// Get header on this response builder.
     ///
     /// When builder has error returns None.
@@ -645,7 +1343,7 @@ impl Builder {
     /// ```
     #[must_use]
     pub fn headers_ref(&self) -> Option<&HeaderMap<HeaderValue>> {
-        self.inner.as_ref().ok().map(|h| &h.headers)
+        self.head_ref().map(|h| &h.headers)
     }
 
     /// Get header on this response builder.
@@ -668,7 +1366,7 @@ impl Builder {
     /// assert_eq!( headers["X-Custom-Foo"], "bar" );
     /// ```
     pub fn headers_mut(&mut self) -> Option<&mut HeaderMap<HeaderValue>> {
-        self.inner.as_mut().ok().map(|h| &mut h.headers)
+        self.head_mut().map(|h| &mut h.headers)
     }
 
     /// Adds an extension to this builder
@@ -691,9 +1389,8 @@ impl Builder {
     where
         T: Clone + Any + Send + Sync + 'static,
     {
-        self.and_then(move |mut head| {
+        self.map(move |head| {
             head.extensions.insert(extension);
-            Ok(head)
         })
     }
 
@@ -712,7 +1409,7 @@ impl Builder {
     /// ```
     #[must_use]
     pub fn extensions_ref(&self) -> Option<&Extensions> {
-        self.inner.as_ref().ok().map(|h| &h.extensions)
+        self.head_ref().map(|h| &h.extensions)
     }
 
     /// Get a mutable reference to the extensions for this response builder.
@@ -730,7 +1427,77 @@ impl Builder {
     /// assert_eq!(extensions.get::<u32>(), Some(&5u32));
     /// ```
     pub fn extensions_mut(&mut self) -> Option<&mut Extensions> {
-        self.inner.as_mut().ok().map(|h| &mut h.extensions)
+        self.head_mut().map(|h| &mut h.extensions)
+    }
+
+    /// Enables rejecting connection-specific headers in HTTP/2 and later
+    /// messages.
+    ///
+    /// `Connection`, `Keep-Alive`, `Proxy-Connection`, `Transfer-Encoding`
+    /// and `Upgrade` make an HTTP/2 or HTTP/3 message malformed (RFC 9113,
+    /// Section 8.2.2), as does `TE` with any value other than `trailers`.
+    /// With this enabled, `body` returns an error if the response has such a
+    /// header, or trailer, and a version of HTTP/2 or later. Earlier
+    /// versions are unaffected.
+    ///
+    /// This is disabled by default.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::*;
+    /// let err = Response::builder()
+    ///     .strict_version_headers(true)
+    ///     .version(Version::HTTP_2)
+    ///     .header("Connection", "close")
+    ///     .body(())
+    ///     .unwrap_err();
+    ///
+    /// assert!(err.is::<header::ConnectionSpecificHeader>());
+    /// ```
+    #[must_use]
+    pub const fn strict_version_headers(mut self, enabled: bool) -> Self {
+        self.strict_version_headers = enabled;
+        self
+    }
+
+    /// Enables or disables collecting every builder error.
+    ///
+    /// By default a builder stops at the first error: every later setter is
+    /// skipped and that error is returned from `body`. With collection
+    /// enabled, setters keep being applied after a failure, and `body`
+    /// returns a single `Error` wrapping a `BuilderErrors` with every failure
+    /// tagged by the component that caused it.
+    ///
+    /// Errors that occurred before collection was enabled are not collected.
+    /// Disabling collection after errors were collected keeps them as the
+    /// builder's error. Once an error is collected, the builder counts as
+    /// having an error, so accessors such as `headers_ref` return `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::*;
+    /// let err = Response::builder()
+    ///     .collect_errors(true)
+    ///     .header("Foo", "Bar\r\n")
+    ///     .status(1000)
+    ///     .body(())
+    ///     .unwrap_err();
+    ///
+    /// let errors = err.get_ref().downcast_ref::<BuilderErrors>().unwrap();
+    /// assert_eq!(errors.len(), 2);
+    /// ```
+    #[must_use]
+    pub fn collect_errors(mut self, enabled: bool) -> Self {
+        if enabled {
+            self.errors.get_or_insert_with(BuilderErrors::new);
+        } else if let Some(errors) = self.errors.take() {
+            if !errors.is_empty() {
+                self.inner = Err(errors.into());
+            }
+        }
+        self
     }
 
     /// "Consumes" this builder, using the provided `body` to return a
@@ -753,19 +1520,133 @@ impl Builder {
     ///     .body(())
     ///     .unwrap();
     /// ```
+    #[cfg_attr(feature = "debug-location", track_caller)]
     pub fn body<T>(self, body: T) -> Result<Response<T>> {
-        self.inner.map(move |head| Response { head, body })
+        self.finish().map(move |head| Response { head, body })
+    }
+
+    /// "Consumes" this builder, returning a constructed `Response` with an
+    /// empty body.
+    ///
+    /// This is equivalent to calling `body(())`.
+    ///
+    /// # Errors
+    ///
+    /// This function may return an error if any previously configured argument
+    /// failed to parse or get converted to the internal representation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::*;
+    ///
+    /// let response = Response::builder()
+    ///     .header("X-Custom-Foo", "Bar")
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(response.headers()["X-Custom-Foo"], "Bar");
+    ///
+    /// let err = Response::builder()
+    ///     .header("Foo", "Bar\r\n")
+    ///     .build()
+    ///     .unwrap_err();
+    ///
+    /// assert!(err.is::<header::InvalidHeaderValue>());
+    /// ```
+    #[cfg_attr(feature = "debug-location", track_caller)]
+    pub fn build(self) -> Result<Response<()>> {
+        self.body(())
+    }
+
+    /// "Consumes" this builder, returning a constructed `Response` with the
+    /// default value of the body type.
+    ///
+    /// # Errors
+    ///
+    /// This function may return an error if any previously configured argument
+    /// failed to parse or get converted to the internal representation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::*;
+    ///
+    /// let response: Response<String> = Response::builder()
+    ///     .body_default()
+    ///     .unwrap();
+    ///
+    /// assert!(response.body().is_empty());
+    /// ```
+    #[cfg_attr(feature = "debug-location", track_caller)]
+    pub fn body_default<T: Default>(self) -> Result<Response<T>> {
+        self.body(T::default())
     }
 
     // private
 
-    fn and_then<F>(self, func: F) -> Self
+    #[cfg_attr(feature = "debug-location", track_caller)]
+    fn finish(self) -> Result<Parts> {
+        let this = if self.strict_version_headers {
+            self.and_then(BuilderField::Header, check_version_headers)
+        } else {
+            self
+        };
+
+        match this.errors {
+            Some(errors) if !errors.is_empty() => Err(errors.into()),
+            _ => this.inner,
+        }
+    }
+
+    /// Returns the head being built, or `None` if the builder has an error,
+    /// including one collected by `collect_errors`.
+    fn head_ref(&self) -> Option<&Parts> {
+        if self.has_collected_errors() {
+            return None;
+        }
+        self.inner.as_ref().ok()
+    }
+
+    fn head_mut(&mut self) -> Option<&mut Parts> {
+        if self.has_collected_errors() {
+            return None;
+        }
+        self.inner.as_mut().ok()
+    }
+
+    fn has_collected_errors(&self) -> bool {
+        self.errors
+            .as_ref()
+            .is_some_and(|errors| !errors.is_empty())
+    }
+
+    #[cfg_attr(feature = "debug-location", track_caller)]
+    fn and_then<F>(mut self, field: BuilderField, func: F) -> Self
     where
-        F: FnOnce(Parts) -> Result<Parts>,
+        F: FnOnce(&mut Parts) -> Result<()>,
     {
-        Self {
-            inner: self.inner.and_then(func),
+        if let Ok(head) = &mut self.inner {
+            if let Err(err) = func(head) {
+                #[cfg(feature = "debug-location")]
+                let err = err.with_location(std::panic::Location::caller());
+                match &mut self.errors {
+                    Some(errors) => errors.push(field, err),
+                    None => self.inner = Err(err),
+                }
+            }
         }
+        self
+    }
+
+    fn map<F>(mut self, func: F) -> Self
+    where
+        F: FnOnce(&mut Parts),
+    {
+        if let Ok(head) = &mut self.inner {
+            func(head);
+        }
+        self
     }
 }
 
@@ -776,6 +1657,181 @@ impl Default for Builder {
     }
 }
 
+/// Rejects connection-specific headers and trailers if `head` is for HTTP/2
+/// or later.
+fn check_version_headers(head: &mut Parts) -> Result<()> {
+    if head.version >= Version::HTTP_2 {
+        head.headers.check_connection_specific()?;
+
+        if let Some(trailers) = head.trailers() {
+            trailers.check_connection_specific()?;
+        }
+    }
+    Ok(())
+}
+
+impl<'a> PseudoHeaders<'a> {
+    /// Returns the `:status` pseudo-header field.
+    #[must_use]
+    pub fn status(&self) -> &'a [u8] {
+        self.status.as_str().as_bytes()
+    }
+
+    /// Returns an iterator over the pseudo-header fields, which is only
+    /// `:status` for a response.
+    pub fn iter(&self) -> impl Iterator<Item = (&'static str, &'a [u8])> + use<'a> {
+        std::iter::once((":status", self.status()))
+    }
+
+    /// Returns an iterator over the regular header fields, leaving out
+    /// connection-specific headers.
+    pub fn headers(&self) -> impl Iterator<Item = (&'a HeaderName, &'a HeaderValue)> + use<'a> {
+        self.headers
+            .iter()
+            .filter(|&(name, value)| !is_connection_specific(name, value))
+    }
+}
+
+impl ReasonPhrase {
+    /// Converts a static string to a `ReasonPhrase`.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the argument contains invalid reason phrase
+    /// characters.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::response::ReasonPhrase;
+    /// let reason = ReasonPhrase::from_static("Not Found Anywhere");
+    /// assert_eq!(reason.as_bytes(), b"Not Found Anywhere");
+    /// ```
+    #[must_use]
+    pub const fn from_static(src: &'static str) -> Self {
+        let bytes = src.as_bytes();
+        let mut i = 0;
+        while i < bytes.len() {
+            assert!(is_valid_reason(bytes[i]), "invalid reason phrase");
+            i += 1;
+        }
+
+        Self(Bytes::from_static(bytes))
+    }
+
+    /// Attempts to convert a slice of bytes to a `ReasonPhrase`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::response::ReasonPhrase;
+    /// assert!(ReasonPhrase::from_bytes(b"Not Found").is_ok());
+    /// assert!(ReasonPhrase::from_bytes(b"Not\r\nFound").is_err());
+    /// ```
+    pub fn from_bytes(src: &[u8]) -> std::result::Result<Self, InvalidReasonPhrase> {
+        Self::from_shared(Bytes::copy_from_slice(src))
+    }
+
+    fn from_shared(src: Bytes) -> std::result::Result<Self, InvalidReasonPhrase> {
+        if src.iter().all(|&b| is_valid_reason(b)) {
+            Ok(Self(src))
+        } else {
+            Err(InvalidReasonPhrase { _priv: () })
+        }
+    }
+
+    /// Returns the reason phrase as a slice of bytes.
+    #[inline]
+    #[must_use]
+    pub fn as_bytes(&self) -> &[u8] {
+        self.0.as_ref()
+    }
+
+    /// Returns the length of the reason phrase in bytes.
+    #[inline]
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns true if the reason phrase is empty.
+    #[inline]
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+#[inline]
+const fn is_valid_reason(b: u8) -> bool {
+    b >= 32 && b != 127 || b == b'\t'
+}
+
+impl AsRef<[u8]> for ReasonPhrase {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl<'a> TryFrom<&'a str> for ReasonPhrase {
+    type Error = InvalidReasonPhrase;
+
+    #[inline]
+    fn try_from(s: &'a str) -> std::result::Result<Self, Self::Error> {
+        Self::from_bytes(s.as_bytes())
+    }
+}
+
+impl<'a> TryFrom<&'a [u8]> for ReasonPhrase {
+    type Error = InvalidReasonPhrase;
+
+    #[inline]
+    fn try_from(s: &'a [u8]) -> std::result::Result<Self, Self::Error> {
+        Self::from_bytes(s)
+    }
+}
+
+impl TryFrom<String> for ReasonPhrase {
+    type Error = InvalidReasonPhrase;
+
+    #[inline]
+    fn try_from(s: String) -> std::result::Result<Self, Self::Error> {
+        Self::from_shared(Bytes::from(s))
+    }
+}
+
+impl TryFrom<Vec<u8>> for ReasonPhrase {
+    type Error = InvalidReasonPhrase;
+
+    #[inline]
+    fn try_from(vec: Vec<u8>) -> std::result::Result<Self, Self::Error> {
+        Self::from_shared(Bytes::from(vec))
+    }
+}
+
+impl fmt::Debug for ReasonPhrase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&String::from_utf8_lossy(self.as_bytes()), f)
+    }
+}
+
+impl fmt::Debug for InvalidReasonPhrase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("InvalidReasonPhrase")
+            // skip _priv noise
+            .finish()
+    }
+}
+
+impl fmt::Display for InvalidReasonPhrase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("invalid reason phrase")
+    }
+}
+
+impl Error for InvalidReasonPhrase {}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -789,4 +1845,420 @@ mod tests {
         });
         assert_eq!(mapped_response.body(), &123u32);
     }
+
+    #[test]
+    fn it_collects_every_builder_error() {
+        let err = Response::builder()
+            .collect_errors(true)
+            .header("Bad Name", "value")
+            .status(1000)
+            .header("X-Good", "bad\r\nvalue")
+            .body(())
+            .unwrap_err();
+
+        let errors = err.get_ref().downcast_ref::<BuilderErrors>().unwrap();
+        let fields: Vec<_> = errors.iter().map(|(field, _)| field).collect();
+        assert_eq!(
+            fields,
+            [
+                BuilderField::Header,
+                BuilderField::Status,
+                BuilderField::Header
+            ]
+        );
+    }
+
+    #[test]
+    fn it_hides_the_head_once_an_error_is_collected() {
+        let mut builder = Response::builder().collect_errors(true).status(204);
+        assert_eq!(builder.status_ref(), Some(&StatusCode::NO_CONTENT));
+        assert!(builder.headers_mut().is_some());
+
+        let mut builder = builder.status(1000).header("X-Good", "value");
+        assert!(builder.status_ref().is_none());
+        assert!(builder.version_ref().is_none());
+        assert!(builder.headers_ref().is_none());
+        assert!(builder.extensions_ref().is_none());
+        assert!(builder.status_mut().is_none());
+        assert!(builder.headers_mut().is_none());
+        assert!(builder.extensions_mut().is_none());
+    }
+
+    #[test]
+    fn it_keeps_collected_errors_when_collection_is_disabled() {
+        let err = Response::builder()
+            .collect_errors(true)
+            .status(1000)
+            .collect_errors(false)
+            .header("Bad Name", "value")
+            .body(())
+            .unwrap_err();
+
+        let errors = err.get_ref().downcast_ref::<BuilderErrors>().unwrap();
+        assert_eq!(errors.len(), 1);
+    }
+
+    #[cfg(feature = "test-util")]
+    #[test]
+    fn it_compares_responses_ignoring_extensions() {
+        let a = Response::builder()
+            .status(StatusCode::CREATED)
+            .header("X-A", "1")
+            .header("X-B", "2")
+            .extension(1u8)
+            .body("body")
+            .unwrap();
+        let b = Response::builder()
+            .status(StatusCode::CREATED)
+            .header("X-B", "2")
+            .header("X-A", "1")
+            .body("body")
+            .unwrap();
+        assert_eq!(a, b);
+
+        let mut c = b.clone();
+        *c.status_mut() = StatusCode::OK;
+        assert_ne!(a, c);
+
+        let mut d = b;
+        d.headers_mut().append("X-A", HeaderValue::from_static("3"));
+        assert_ne!(a, d);
+    }
+
+    #[cfg(feature = "test-util")]
+    #[test]
+    fn it_compares_response_parts() {
+        let (mut a, ()) = Response::new(()).into_parts();
+        let (b, ()) = Response::new(()).into_parts();
+        a.extensions.insert("ignored");
+        assert_eq!(a, b);
+
+        a.version = Version::HTTP_2;
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn it_can_be_created_from_a_status_code() {
+        let response = Response::from(StatusCode::NO_CONTENT);
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+        assert_eq!(response.version(), Version::HTTP_11);
+        assert!(response.headers().is_empty());
+
+        let mut headers = HeaderMap::new();
+        headers.insert("Location", HeaderValue::from_static("/foo"));
+        let response: Response<()> = (StatusCode::SEE_OTHER, headers).into();
+        assert_eq!(response.status(), StatusCode::SEE_OTHER);
+        assert_eq!(response.headers()["Location"], "/foo");
+    }
+
+    #[test]
+    fn it_estimates_the_head_size() {
+        let response = Response::builder()
+            .status(StatusCode::NOT_FOUND)
+            .header("Content-Type", "text/plain")
+            .header("X-Multi", "a")
+            .header("X-Multi", "bb")
+            .body(())
+            .unwrap();
+        let wire = "HTTP/1.1 404 Not Found\r\n\
+                    content-type: text/plain\r\n\
+                    x-multi: a\r\n\
+                    x-multi: bb\r\n\
+                    \r\n";
+        assert_eq!(response.head_size(4), wire.len());
+        assert_eq!(response.head_size(32), response.head_size(0) + 3 * 32);
+
+        // Unknown status codes have no reason phrase.
+        let response = Response::builder().status(599).body(()).unwrap();
+        assert_eq!(response.head_size(4), "HTTP/1.1 599 \r\n\r\n".len());
+    }
+
+    #[test]
+    fn it_round_trips_a_custom_reason_phrase() {
+        let response = Response::builder()
+            .status(StatusCode::NOT_FOUND)
+            .reason("Not Found Anywhere")
+            .body(())
+            .unwrap();
+        assert_eq!(response.reason().unwrap().as_bytes(), b"Not Found Anywhere");
+        assert_eq!(
+            response.head_size(4),
+            "HTTP/1.1 404 Not Found Anywhere\r\n\r\n".len()
+        );
+
+        let (mut parts, body) = response.into_parts();
+        let reason = parts.reason().cloned();
+        parts.set_reason(None);
+        assert!(parts.reason().is_none());
+        parts.set_reason(reason);
+
+        let response = Response::from_parts(parts, body);
+        assert_eq!(response.reason().unwrap().as_bytes(), b"Not Found Anywhere");
+    }
+
+    #[test]
+    fn it_clears_the_reason_phrase_when_setting_the_status() {
+        let mut response = Response::builder()
+            .status(StatusCode::OK)
+            .reason("Fine")
+            .body(())
+            .unwrap();
+
+        // Writing through `status_mut` keeps the phrase.
+        *response.status_mut() = StatusCode::ACCEPTED;
+        assert_eq!(response.reason().unwrap().as_bytes(), b"Fine");
+
+        response.set_status(StatusCode::NOT_FOUND);
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert!(response.reason().is_none());
+
+        let (mut parts, ()) = response.into_parts();
+        parts.set_reason(Some(ReasonPhrase::from_static("Fine")));
+        parts.set_status(StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(parts.status, StatusCode::INTERNAL_SERVER_ERROR);
+        assert!(parts.reason().is_none());
+    }
+
+    #[test]
+    fn it_rejects_an_invalid_reason_phrase() {
+        for invalid in ["Not\nFound", "Not\rFound", "Not\0Found", "Not\x7fFound"] {
+            assert!(ReasonPhrase::try_from(invalid).is_err(), "{invalid:?}");
+        }
+
+        assert!(ReasonPhrase::from_bytes(b"\tOpaque \xff").is_ok());
+        assert!(ReasonPhrase::from_bytes(b"").is_ok());
+
+        let err = Response::builder()
+            .reason("Not\r\nFound")
+            .body(())
+            .unwrap_err();
+        assert!(err.is::<InvalidReasonPhrase>());
+    }
+
+    #[test]
+    fn it_sets_typed_headers_like_header() {
+        let location = Uri::from_static("/elsewhere");
+        let typed = Response::builder()
+            .content_type("application/json")
+            .content_length(2)
+            .cache_control("no-cache")
+            .location(&location)
+            .body(())
+            .unwrap();
+        let manual = Response::builder()
+            .header(CONTENT_TYPE, "application/json")
+            .header(CONTENT_LENGTH, 2u64)
+            .header(CACHE_CONTROL, "no-cache")
+            .header(LOCATION, "/elsewhere")
+            .body(())
+            .unwrap();
+        assert_eq!(typed.headers(), manual.headers());
+
+        let err = Response::builder()
+            .content_type("text/html\r\n")
+            .body(())
+            .unwrap_err();
+        assert!(err.is::<crate::header::InvalidHeaderValue>());
+    }
+
+    #[test]
+    fn it_supports_trailers() {
+        let mut response = Response::builder()
+            .header("Trailer", "grpc-status, grpc-message")
+            .trailer("grpc-status", "0")
+            .trailer("grpc-message", "ok")
+            .body(())
+            .unwrap();
+
+        let trailers = response.trailers().unwrap();
+        assert_eq!(trailers.len(), 2);
+        assert_eq!(trailers["grpc-status"], "0");
+        assert!(!response.headers().contains_key(&"grpc-status"));
+
+        response
+            .trailers_mut()
+            .insert("grpc-status", HeaderValue::from_static("13"));
+        assert_eq!(response.trailers().unwrap()["grpc-status"], "13");
+
+        let (mut parts, ()) = response.into_parts();
+        assert_eq!(parts.take_trailers().unwrap().len(), 2);
+        assert!(parts.trailers().is_none());
+
+        let mut trailers = Trailers::new();
+        trailers.insert("grpc-status", HeaderValue::from_static("14"));
+        assert!(parts.set_trailers(trailers).is_none());
+        let mut response = Response::from_parts(parts, ());
+        let previous = response.set_trailers(Trailers::new()).unwrap();
+        assert_eq!(previous["grpc-status"], "14");
+
+        let (parts, (), trailers) = response.into_parts_with_trailers();
+        assert!(parts.trailers().is_none());
+        assert!(trailers.unwrap().is_empty());
+
+        let (_, (), trailers) = Response::new(()).into_parts_with_trailers();
+        assert!(trailers.is_none());
+
+        let err = Response::builder()
+            .collect_errors(true)
+            .trailer("grpc-status", "bad\r\n")
+            .body(())
+            .unwrap_err();
+        let errors = err.get_ref().downcast_ref::<BuilderErrors>().unwrap();
+        assert_eq!(errors.iter().next().unwrap().0, BuilderField::Trailer);
+    }
+
+    #[test]
+    fn it_does_not_allocate_unused_trailers() {
+        let response = Response::builder()
+            .header("Content-Type", "text/plain")
+            .body(())
+            .unwrap();
+        assert!(response.trailers().is_none());
+        assert_eq!(
+            std::mem::size_of::<Option<Box<HeaderMap<HeaderValue>>>>(),
+            std::mem::size_of::<usize>()
+        );
+    }
+
+    #[test]
+    fn strict_version_headers_rejects_connection_specific_headers() {
+        let err = Response::builder()
+            .strict_version_headers(true)
+            .version(Version::HTTP_2)
+            .header("Keep-Alive", "timeout=5")
+            .body(())
+            .unwrap_err();
+        assert!(err.is::<crate::header::ConnectionSpecificHeader>());
+
+        let err = Response::builder()
+            .collect_errors(true)
+            .strict_version_headers(true)
+            .version(Version::HTTP_2)
+            .header("Upgrade", "websocket")
+            .body(())
+            .unwrap_err();
+        let errors = err.get_ref().downcast_ref::<BuilderErrors>().unwrap();
+        assert_eq!(errors.iter().next().unwrap().0, BuilderField::Header);
+
+        let res = Response::builder()
+            .strict_version_headers(true)
+            .version(Version::HTTP_11)
+            .header("Upgrade", "websocket")
+            .body(());
+        assert!(res.is_ok());
+    }
+
+    #[test]
+    fn it_converts_the_body_type_keeping_parts() {
+        let mut original = Response::builder()
+            .header("x-custom", "yes")
+            .body(b"hello".to_vec())
+            .unwrap();
+        original.extensions_mut().insert(7u8);
+
+        let bytes: Response<bytes::Bytes> = original.into_body_type();
+        assert_eq!(bytes.body(), &bytes::Bytes::from_static(b"hello"));
+        assert_eq!(bytes.headers()["x-custom"], "yes");
+        assert_eq!(bytes.extensions().get::<u8>(), Some(&7));
+
+        let vec: Response<Vec<u8>> = bytes.into_body_type();
+        assert_eq!(vec.body(), b"hello");
+        assert_eq!(vec.extensions().get::<u8>(), Some(&7));
+
+        let string: Response<String> = Response::new("hi").into_body_type();
+        let bytes: Response<bytes::Bytes> = string.into_body_type();
+        assert_eq!(bytes.body(), "hi");
+    }
+
+    #[test]
+    fn it_reads_staged_components_back() {
+        let mut builder = Response::builder()
+            .status(StatusCode::ACCEPTED)
+            .version(Version::HTTP_2);
+        assert_eq!(builder.status_ref(), Some(&StatusCode::ACCEPTED));
+        assert_eq!(builder.version_ref(), Some(&Version::HTTP_2));
+
+        *builder.status_mut().unwrap() = StatusCode::NO_CONTENT;
+        let response = builder.body(()).unwrap();
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+        let mut builder = Response::builder().status(1000);
+        assert!(builder.status_ref().is_none());
+        assert!(builder.version_ref().is_none());
+        assert!(builder.status_mut().is_none());
+        assert!(builder.version_mut().is_none());
+    }
+
+    #[test]
+    fn it_maps_to_pseudo_headers() {
+        let response = Response::builder()
+            .status(StatusCode::CREATED)
+            .reason("Made It")
+            .header("location", "/items/1")
+            .header("connection", "close")
+            .header("te", "trailers")
+            .body(())
+            .unwrap();
+        let (parts, ()) = response.into_parts();
+
+        let pseudo = parts.to_pseudo_headers();
+        assert_eq!(
+            pseudo.iter().collect::<Vec<_>>(),
+            [(":status", &b"201"[..])]
+        );
+        let headers: Vec<_> = pseudo.headers().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(headers, ["location", "te"]);
+    }
+
+    #[test]
+    fn it_clones_deeply() {
+        let original = Response::builder()
+            .status(StatusCode::OK)
+            .header("etag", "\"v1\"")
+            .trailer("grpc-status", "0")
+            .extension(vec![1u8, 2])
+            .body(String::from("cached"))
+            .unwrap();
+
+        let mut copy = original.clone();
+        assert_eq!(copy.status(), original.status());
+        assert_eq!(copy.headers(), original.headers());
+        assert_eq!(copy.trailers(), original.trailers());
+        assert_eq!(copy.body(), original.body());
+
+        copy.headers_mut().clear();
+        copy.trailers_mut().clear();
+        copy.extensions_mut().get_mut::<Vec<u8>>().unwrap().push(3);
+
+        assert_eq!(original.headers()["etag"], "\"v1\"");
+        assert_eq!(original.trailers().unwrap()["grpc-status"], "0");
+        assert_eq!(original.extensions().get::<Vec<u8>>(), Some(&vec![1, 2]));
+        assert_eq!(copy.extensions().get::<Vec<u8>>(), Some(&vec![1, 2, 3]));
+    }
+
+    #[cfg(feature = "debug-location")]
+    #[test]
+    fn errors_record_the_offending_setter_location() {
+        let line = line!() + 3;
+        let err = Response::builder()
+            .version(Version::HTTP_11)
+            .status(1000u16)
+            .body(())
+            .unwrap_err();
+
+        let location = err.location().unwrap();
+        assert_eq!(location.file(), file!());
+        assert_eq!(location.line(), line);
+        assert!(err.to_string().ends_with(&format!(" (at {location})")));
+
+        let line = line!() + 2;
+        let err = Response::builder()
+            .cache_control("no-store\n")
+            .body(())
+            .unwrap_err();
+
+        let location = err.location().unwrap();
+        assert_eq!(location.file(), file!());
+        assert_eq!(location.line(), line);
+    }
 }
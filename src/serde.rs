@@ -0,0 +1,510 @@
+//! Support for serializing and deserializing HTTP types with `serde`.
+//!
+//! [`StatusCode`], [`Method`], [`Version`], [`Uri`], [`Authority`] and
+//! [`Scheme`] implement `Serialize` and `Deserialize` directly:
+//!
+//! * `StatusCode` is an integer, in human-readable and compact formats
+//!   alike.
+//! * `Method`, `Scheme` and `Authority` are their string form.
+//! * `Version` is its `HTTP/x.y` string.
+//! * `Uri` is its string form.
+//!
+//! Deserializing goes through the same validating parsers as `FromStr`. Any
+//! parse error is reported with its message.
+//!
+//! A [`HeaderMap`](crate::HeaderMap) has no single obvious representation,
+//! so it doesn't implement the traits. Instead, [`header_map`] provides a
+//! `serialize` and a `deserialize` function, to opt in per field with
+//! `#[serde(with = "...")]`. [`header_map_seq`] is the same, except that it
+//! always serializes values as a sequence. The [`uri`], [`method`] and
+//! [`status_code`] modules do the same for their types, matching the trait
+//! impls:
+//!
+//! ```ignore
+//! use http::{HeaderMap, Method, StatusCode, Uri};
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Recorded {
+//!     method: Method,
+//!     uri: Uri,
+//!     status: StatusCode,
+//!     #[serde(with = "http::serde::header_map")]
+//!     headers: HeaderMap,
+//! }
+//! ```
+//!
+//! This module requires the `serde` feature.
+
+use std::fmt;
+use std::marker::PhantomData;
+use std::str::FromStr;
+
+use ::serde::{Deserialize, Deserializer, Serialize, Serializer, de};
+
+use crate::uri::{Authority, Scheme};
+use crate::{Method, StatusCode, Uri, Version};
+
+/// Deserializes a string with the `FromStr` implementation of `T`.
+struct FromStrVisitor<T> {
+    expecting: &'static str,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T> FromStrVisitor<T> {
+    const fn new(expecting: &'static str) -> Self {
+        Self {
+            expecting,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T> de::Visitor<'_> for FromStrVisitor<T>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    type Value = T;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.expecting)
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<T, E> {
+        v.parse().map_err(E::custom)
+    }
+}
+
+macro_rules! serde_str {
+    ($($ty:ty => $expecting:expr,)+) => {$(
+        impl Serialize for $ty {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.collect_str(self)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $ty {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                deserializer.deserialize_str(FromStrVisitor::new($expecting))
+            }
+        }
+    )+};
+}
+
+serde_str! {
+    Method => "an HTTP method",
+    Version => "an HTTP version",
+    Uri => "a URI",
+    Authority => "a URI authority",
+    Scheme => "a URI scheme",
+}
+
+impl Serialize for StatusCode {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u16(self.as_u16())
+    }
+}
+
+impl<'de> Deserialize<'de> for StatusCode {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_u16(StatusCodeVisitor)
+    }
+}
+
+struct StatusCodeVisitor;
+
+impl de::Visitor<'_> for StatusCodeVisitor {
+    type Value = StatusCode;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("an HTTP status code from 100 to 999")
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<StatusCode, E> {
+        u16::try_from(v)
+            .ok()
+            .and_then(|v| StatusCode::from_u16(v).ok())
+            .ok_or_else(|| E::invalid_value(de::Unexpected::Unsigned(v), &self))
+    }
+
+    fn visit_i64<E: de::Error>(self, v: i64) -> Result<StatusCode, E> {
+        u64::try_from(v)
+            .map_err(|_| E::invalid_value(de::Unexpected::Signed(v), &self))
+            .and_then(|v| self.visit_u64(v))
+    }
+}
+
+/// Serializes a [`Uri`] as a string.
+///
+/// This matches the `Serialize` and `Deserialize` implementations of `Uri`.
+///
+/// # Examples
+///
+/// ```
+/// # use http::Uri;
+/// let uri = Uri::from_static("https://example.com/a?b=c");
+///
+/// let mut json = Vec::new();
+/// http::serde::uri::serialize(&uri, &mut serde_json::Serializer::new(&mut json)).unwrap();
+/// assert_eq!(json, br#""https://example.com/a?b=c""#);
+///
+/// let mut de = serde_json::Deserializer::from_slice(&json);
+/// assert_eq!(http::serde::uri::deserialize(&mut de).unwrap(), uri);
+/// ```
+pub mod uri {
+    use ::serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use crate::Uri;
+
+    /// Serializes `uri` as a string.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error from the serializer.
+    pub fn serialize<S: Serializer>(uri: &Uri, serializer: S) -> Result<S::Ok, S::Error> {
+        uri.serialize(serializer)
+    }
+
+    /// Deserializes a `Uri` from a string.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the input is not a string or not a valid URI.
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Uri, D::Error> {
+        Uri::deserialize(deserializer)
+    }
+}
+
+/// Serializes a [`Method`] as a string.
+///
+/// This matches the `Serialize` and `Deserialize` implementations of
+/// `Method`.
+///
+/// # Examples
+///
+/// ```
+/// # use http::Method;
+/// let mut json = Vec::new();
+/// http::serde::method::serialize(&Method::PATCH, &mut serde_json::Serializer::new(&mut json))
+///     .unwrap();
+/// assert_eq!(json, br#""PATCH""#);
+///
+/// let mut de = serde_json::Deserializer::from_slice(&json);
+/// assert_eq!(http::serde::method::deserialize(&mut de).unwrap(), Method::PATCH);
+/// ```
+pub mod method {
+    use ::serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use crate::Method;
+
+    /// Serializes `method` as a string.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error from the serializer.
+    pub fn serialize<S: Serializer>(method: &Method, serializer: S) -> Result<S::Ok, S::Error> {
+        method.serialize(serializer)
+    }
+
+    /// Deserializes a `Method` from a string.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the input is not a string or not a valid method.
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Method, D::Error> {
+        Method::deserialize(deserializer)
+    }
+}
+
+/// Serializes a [`StatusCode`] as an integer.
+///
+/// This matches the `Serialize` and `Deserialize` implementations of
+/// `StatusCode`.
+///
+/// # Examples
+///
+/// ```
+/// # use http::StatusCode;
+/// let mut json = Vec::new();
+/// let mut ser = serde_json::Serializer::new(&mut json);
+/// http::serde::status_code::serialize(&StatusCode::NOT_FOUND, &mut ser).unwrap();
+/// assert_eq!(json, b"404");
+///
+/// let mut de = serde_json::Deserializer::from_slice(&json);
+/// assert_eq!(
+///     http::serde::status_code::deserialize(&mut de).unwrap(),
+///     StatusCode::NOT_FOUND
+/// );
+/// ```
+pub mod status_code {
+    use ::serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use crate::StatusCode;
+
+    /// Serializes `status` as an integer.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error from the serializer.
+    pub fn serialize<S: Serializer>(status: &StatusCode, serializer: S) -> Result<S::Ok, S::Error> {
+        status.serialize(serializer)
+    }
+
+    /// Deserializes a `StatusCode` from an integer.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the input is not an integer from 100 to 999.
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<StatusCode, D::Error> {
+        StatusCode::deserialize(deserializer)
+    }
+}
+
+/// Serializes a [`HeaderMap`](crate::HeaderMap) as a map from header names
+/// to values.
+///
+/// A name with a single value maps to that value, and a name with several
+/// values maps to a sequence of them. Values are strings if they are visible
+/// ASCII, and bytes otherwise. Deserializing accepts any of these forms, and
+/// requires a self-describing format. Values that are not visible ASCII only
+/// round trip in formats with a native bytes type, unlike JSON.
+///
+/// # Examples
+///
+/// ```
+/// # use http::{HeaderMap, HeaderValue};
+/// let mut headers = HeaderMap::new();
+/// headers.insert("content-type", HeaderValue::from_static("text/html"));
+/// headers.append("set-cookie", HeaderValue::from_static("a=1"));
+/// headers.append("set-cookie", HeaderValue::from_static("b=2"));
+///
+/// let mut json = Vec::new();
+/// let mut ser = serde_json::Serializer::new(&mut json);
+/// http::serde::header_map::serialize(&headers, &mut ser).unwrap();
+/// assert_eq!(
+///     json,
+///     br#"{"content-type":"text/html","set-cookie":["a=1","b=2"]}"#
+/// );
+///
+/// let mut de = serde_json::Deserializer::from_slice(&json);
+/// assert_eq!(http::serde::header_map::deserialize(&mut de).unwrap(), headers);
+/// ```
+pub mod header_map {
+    use std::fmt;
+
+    use ::serde::ser::SerializeMap;
+    use ::serde::{Deserialize, Deserializer, Serialize, Serializer, de};
+
+    use crate::header::{GetAll, HeaderMap, HeaderName, HeaderValue};
+
+    /// Serializes `headers` as a map from names to values.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error from the serializer.
+    pub fn serialize<S: Serializer>(
+        headers: &HeaderMap<HeaderValue>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(headers.keys_len()))?;
+
+        for name in headers.keys() {
+            let values = headers.get_all(name);
+            let mut iter = values.iter();
+
+            match (iter.next(), iter.next()) {
+                (Some(value), None) => map.serialize_entry(name.as_str(), &Value(value))?,
+                _ => map.serialize_entry(name.as_str(), &Values(values))?,
+            }
+        }
+
+        map.end()
+    }
+
+    /// Deserializes a `HeaderMap` from a map from names to values.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the input is not a map, or contains an invalid
+    /// header name or value.
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<HeaderMap<HeaderValue>, D::Error> {
+        deserializer.deserialize_map(MapVisitor)
+    }
+
+    struct Value<'a>(&'a HeaderValue);
+
+    impl Serialize for Value<'_> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            match self.0.to_str() {
+                Ok(value) => serializer.serialize_str(value),
+                Err(_) => serializer.serialize_bytes(self.0.as_bytes()),
+            }
+        }
+    }
+
+    pub(super) struct Values<'a>(pub(super) GetAll<'a, HeaderValue>);
+
+    impl Serialize for Values<'_> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.collect_seq(self.0.iter().map(Value))
+        }
+    }
+
+    struct MapVisitor;
+
+    impl<'de> de::Visitor<'de> for MapVisitor {
+        type Value = HeaderMap<HeaderValue>;
+
+        fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("a map of header names to values")
+        }
+
+        fn visit_map<A: de::MapAccess<'de>>(self, mut access: A) -> Result<Self::Value, A::Error> {
+            let mut headers = HeaderMap::with_capacity(access.size_hint().unwrap_or(0));
+
+            while let Some(name) = access.next_key::<String>()? {
+                let name = HeaderName::from_bytes(name.as_bytes()).map_err(de::Error::custom)?;
+
+                for value in access.next_value::<OneOrMany>()?.0 {
+                    headers.append(name.clone(), value);
+                }
+            }
+
+            Ok(headers)
+        }
+    }
+
+    /// One header value, or a sequence of them.
+    struct OneOrMany(Vec<HeaderValue>);
+
+    impl<'de> Deserialize<'de> for OneOrMany {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            deserializer.deserialize_any(ValueVisitor).map(OneOrMany)
+        }
+    }
+
+    /// A single header value.
+    struct One(HeaderValue);
+
+    impl<'de> Deserialize<'de> for One {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            deserializer.deserialize_any(OneVisitor).map(One)
+        }
+    }
+
+    struct OneVisitor;
+
+    impl de::Visitor<'_> for OneVisitor {
+        type Value = HeaderValue;
+
+        fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("a header value")
+        }
+
+        fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+            self.visit_bytes(v.as_bytes())
+        }
+
+        fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+            HeaderValue::from_bytes(v).map_err(E::custom)
+        }
+    }
+
+    struct ValueVisitor;
+
+    impl<'de> de::Visitor<'de> for ValueVisitor {
+        type Value = Vec<HeaderValue>;
+
+        fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("a header value or a sequence of header values")
+        }
+
+        fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+            OneVisitor.visit_str(v).map(|value| vec![value])
+        }
+
+        fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+            OneVisitor.visit_bytes(v).map(|value| vec![value])
+        }
+
+        fn visit_seq<A: de::SeqAccess<'de>>(self, mut access: A) -> Result<Self::Value, A::Error> {
+            let mut values = Vec::with_capacity(access.size_hint().unwrap_or(0));
+
+            while let Some(One(value)) = access.next_element()? {
+                values.push(value);
+            }
+
+            Ok(values)
+        }
+    }
+}
+
+/// Serializes a [`HeaderMap`](crate::HeaderMap) as a map from header names
+/// to sequences of values.
+///
+/// This is like [`header_map`], except that every name maps to a sequence,
+/// even if it has a single value. This gives consumers a uniform shape.
+/// Deserializing is the same as for [`header_map`], and accepts both forms.
+///
+/// # Examples
+///
+/// ```
+/// # use http::{HeaderMap, HeaderValue};
+/// let mut headers = HeaderMap::new();
+/// headers.insert("content-type", HeaderValue::from_static("text/html"));
+/// headers.append("set-cookie", HeaderValue::from_static("a=1"));
+/// headers.append("set-cookie", HeaderValue::from_static("b=2"));
+///
+/// let mut json = Vec::new();
+/// let mut ser = serde_json::Serializer::new(&mut json);
+/// http::serde::header_map_seq::serialize(&headers, &mut ser).unwrap();
+/// assert_eq!(
+///     json,
+///     br#"{"content-type":["text/html"],"set-cookie":["a=1","b=2"]}"#
+/// );
+///
+/// let mut de = serde_json::Deserializer::from_slice(&json);
+/// assert_eq!(http::serde::header_map_seq::deserialize(&mut de).unwrap(), headers);
+/// ```
+pub mod header_map_seq {
+    use ::serde::ser::SerializeMap;
+    use ::serde::{Deserializer, Serializer};
+
+    use super::header_map::{self, Values};
+    use crate::header::{HeaderMap, HeaderValue};
+
+    /// Serializes `headers` as a map from names to sequences of values.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error from the serializer.
+    pub fn serialize<S: Serializer>(
+        headers: &HeaderMap<HeaderValue>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(headers.keys_len()))?;
+
+        for name in headers.keys() {
+            map.serialize_entry(name.as_str(), &Values(headers.get_all(name)))?;
+        }
+
+        map.end()
+    }
+
+    /// Deserializes a `HeaderMap` from a map from names to values.
+    ///
+    /// This is the same as [`header_map::deserialize`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the input is not a map, or contains an invalid
+    /// header name or value.
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<HeaderMap<HeaderValue>, D::Error> {
+        header_map::deserialize(deserializer)
+    }
+}
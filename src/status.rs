@@ -18,6 +18,7 @@ use std::convert::TryFrom;
 use std::error::Error;
 use std::fmt;
 use std::num::NonZeroU16;
+use std::ops::RangeInclusive;
 use std::str::FromStr;
 
 /// An HTTP status code (`status-code` in RFC 9110 et al.).
@@ -48,10 +49,75 @@ pub struct StatusCode(NonZeroU16);
 ///
 /// This error indicates that the supplied input was not a valid number, was less
 /// than 100, or was greater than 999.
+#[derive(Clone)]
 pub struct InvalidStatusCode {
     _priv: (),
 }
 
+/// The class of a status code, given by its first digit.
+///
+/// Returned by [`StatusCode::class`].
+///
+/// # Examples
+///
+/// ```
+/// use http::StatusCode;
+/// use http::status::StatusClass;
+///
+/// fn should_retry(status: StatusCode) -> bool {
+///     match status.class() {
+///         StatusClass::ServerError => true,
+///         StatusClass::ClientError => status == StatusCode::TOO_MANY_REQUESTS,
+///         StatusClass::Informational
+///         | StatusClass::Success
+///         | StatusClass::Redirection
+///         | StatusClass::Unclassified => false,
+///     }
+/// }
+///
+/// assert!(should_retry(StatusCode::BAD_GATEWAY));
+/// assert!(!should_retry(StatusCode::NOT_FOUND));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum StatusClass {
+    /// 100-199
+    Informational,
+    /// 200-299
+    Success,
+    /// 300-399
+    Redirection,
+    /// 400-499
+    ClientError,
+    /// 500-599
+    ServerError,
+    /// 600-999, which have no defined meaning.
+    Unclassified,
+}
+
+impl StatusClass {
+    /// Returns the inclusive range of status codes in this class.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use http::status::StatusClass;
+    ///
+    /// assert_eq!(StatusClass::Success.range(), 200..=299);
+    /// assert_eq!(StatusClass::Unclassified.range(), 600..=999);
+    /// ```
+    #[must_use]
+    pub const fn range(self) -> RangeInclusive<u16> {
+        match self {
+            Self::Informational => 100..=199,
+            Self::Success => 200..=299,
+            Self::Redirection => 300..=399,
+            Self::ClientError => 400..=499,
+            Self::ServerError => 500..=599,
+            Self::Unclassified => 600..=999,
+        }
+    }
+}
+
 impl StatusCode {
     #[must_use]
     pub const fn new() -> Self {
@@ -75,14 +141,41 @@ impl StatusCode {
     /// assert!(err.is_err());
     /// ```
     #[inline]
-    pub fn from_u16(src: u16) -> Result<Self, InvalidStatusCode> {
-        if !(100..1000).contains(&src) {
+    pub const fn from_u16(src: u16) -> Result<Self, InvalidStatusCode> {
+        if src < 100 || src >= 1000 {
             return Err(InvalidStatusCode::new());
         }
 
-        NonZeroU16::new(src)
-            .map(StatusCode)
-            .ok_or_else(InvalidStatusCode::new)
+        match NonZeroU16::new(src) {
+            Some(code) => Ok(Self(code)),
+            None => Err(InvalidStatusCode::new()),
+        }
+    }
+
+    /// Converts an offset from `100` to an informational (1xx) status code.
+    ///
+    /// An offset of `0` is `100 Continue`, `1` is `101 Switching Protocols`,
+    /// and so on up to `9` for `109`. Returns `None` for larger offsets.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use http::StatusCode;
+    ///
+    /// assert_eq!(StatusCode::from_informational_offset(0), Some(StatusCode::CONTINUE));
+    /// assert_eq!(StatusCode::from_informational_offset(2), Some(StatusCode::PROCESSING));
+    /// assert_eq!(StatusCode::from_informational_offset(10), None);
+    /// ```
+    #[must_use]
+    pub const fn from_informational_offset(offset: u8) -> Option<Self> {
+        if offset > 9 {
+            return None;
+        }
+
+        match NonZeroU16::new(100 + offset as u16) {
+            Some(code) => Some(Self(code)),
+            None => None,
+        }
     }
 
     /// Converts a `&[u8]` to a status code.
@@ -180,6 +273,51 @@ impl StatusCode {
         canonical_reason(self.0.get())
     }
 
+    /// Get the standardised `reason-phrase` for this status code as bytes.
+    ///
+    /// This is the same as [`canonical_reason`](Self::canonical_reason), for
+    /// writing into a byte buffer.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let status = http::StatusCode::NOT_FOUND;
+    /// assert_eq!(status.canonical_reason_bytes(), Some(&b"Not Found"[..]));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn canonical_reason_bytes(&self) -> Option<&'static [u8]> {
+        match canonical_reason(self.0.get()) {
+            Some(reason) => Some(reason.as_bytes()),
+            None => None,
+        }
+    }
+
+    /// Get the status code followed by its canonical reason, such as
+    /// `200 OK`, as bytes.
+    ///
+    /// This is the `status-code SP reason-phrase` part of an HTTP/1.1 status
+    /// line, and matches the `Display` output. Each one is a static string
+    /// built at compile time, so servers can copy it as is instead of
+    /// formatting it. Returns `None` if the status code has no canonical
+    /// reason.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use http::StatusCode;
+    /// assert_eq!(StatusCode::OK.status_line_bytes(), Some(&b"200 OK"[..]));
+    /// assert_eq!(StatusCode::from_u16(299).unwrap().status_line_bytes(), None);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn status_line_bytes(&self) -> Option<&'static [u8]> {
+        match status_line(self.0.get()) {
+            Some(line) => Some(line.as_bytes()),
+            None => None,
+        }
+    }
+
     /// Check if status is within 100-199.
     #[inline]
     #[must_use]
@@ -214,6 +352,39 @@ impl StatusCode {
     pub fn is_server_error(&self) -> bool {
         (500..600).contains(&self.0.get())
     }
+
+    /// Check if status is within 400-599, meaning either a client or a server
+    /// error.
+    #[inline]
+    #[must_use]
+    pub const fn is_error(&self) -> bool {
+        let code = self.0.get();
+        code >= 400 && code < 600
+    }
+
+    /// Returns the class of this status code.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use http::StatusCode;
+    /// use http::status::StatusClass;
+    ///
+    /// assert_eq!(StatusCode::OK.class(), StatusClass::Success);
+    /// assert_eq!(StatusCode::from_u16(799).unwrap().class(), StatusClass::Unclassified);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn class(&self) -> StatusClass {
+        match self.0.get() {
+            100..=199 => StatusClass::Informational,
+            200..=299 => StatusClass::Success,
+            300..=399 => StatusClass::Redirection,
+            400..=499 => StatusClass::ClientError,
+            500..=599 => StatusClass::ServerError,
+            _ => StatusClass::Unclassified,
+        }
+    }
 }
 
 impl fmt::Debug for StatusCode {
@@ -241,6 +412,22 @@ impl fmt::Display for StatusCode {
     }
 }
 
+/// Allows a `StatusCode` to be used directly as an error type.
+///
+/// The error message is the `Display` output, e.g. `404 Not Found`. Combined
+/// with the standard library's `From<E: Error> for Box<dyn Error>`, this
+/// allows `StatusCode::NOT_FOUND.into()` to produce a boxed error.
+///
+/// # Example
+///
+/// ```
+/// # use http::StatusCode;
+/// let err: Box<dyn std::error::Error> = StatusCode::NOT_FOUND.into();
+/// assert_eq!(err.to_string(), "404 Not Found");
+/// ```
+#[cfg(feature = "error")]
+impl Error for StatusCode {}
+
 impl Default for StatusCode {
     #[inline]
     fn default() -> Self {
@@ -334,6 +521,15 @@ macro_rules! status_codes {
                 _ => None
             }
         }
+
+        const fn status_line(num: u16) -> Option<&'static str> {
+            match num {
+                $(
+                $num => Some(concat!($num, " ", $phrase)),
+                )+
+                _ => None
+            }
+        }
     }
 }
 
@@ -347,6 +543,9 @@ status_codes! {
     /// 102 Processing
     /// [[RFC2518, Section 10.1](https://datatracker.ietf.org/doc/html/rfc2518#section-10.1)]
     (102, PROCESSING, "Processing");
+    /// 103 Early Hints
+    /// [[RFC8297, Section 2](https://datatracker.ietf.org/doc/html/rfc8297#section-2)]
+    (103, EARLY_HINTS, "Early Hints");
 
     /// 200 OK
     /// [[RFC9110, Section 15.3.1](https://datatracker.ietf.org/doc/html/rfc9110#section-15.3.1)]
@@ -466,9 +665,12 @@ status_codes! {
     /// 421 Misdirected Request
     /// [[RFC9110, Section 15.5.20](https://datatracker.ietf.org/doc/html/rfc9110#section-15.5.20)]
     (421, MISDIRECTED_REQUEST, "Misdirected Request");
-    /// 422 Unprocessable Entity
+    /// 422 Unprocessable Content
     /// [[RFC9110, Section 15.5.21](https://datatracker.ietf.org/doc/html/rfc9110#section-15.5.21)]
-    (422, UNPROCESSABLE_ENTITY, "Unprocessable Entity");
+    ///
+    /// Formerly named "Unprocessable Entity". The canonical reason phrase is
+    /// unchanged for compatibility.
+    (422, UNPROCESSABLE_CONTENT, "Unprocessable Entity");
     /// 423 Locked
     /// [[RFC4918, Section 11.3](https://datatracker.ietf.org/doc/html/rfc4918#section-11.3)]
     (423, LOCKED, "Locked");
@@ -535,6 +737,12 @@ status_codes! {
     (511, NETWORK_AUTHENTICATION_REQUIRED, "Network Authentication Required");
 }
 
+impl StatusCode {
+    /// 422 Unprocessable Entity
+    #[deprecated(note = "renamed to `UNPROCESSABLE_CONTENT` by RFC 9110")]
+    pub const UNPROCESSABLE_ENTITY: Self = Self::UNPROCESSABLE_CONTENT;
+}
+
 impl InvalidStatusCode {
     const fn new() -> Self {
         Self { _priv: () }
@@ -5,7 +5,7 @@ use std::{cmp, fmt, str};
 
 use bytes::Bytes;
 
-use super::{ErrorKind, InvalidUri, Port, URI_CHARS};
+use super::{ErrorKind, InvalidUri, Port, Scheme, URI_CHARS};
 use crate::byte_str::ByteStr;
 
 /// Represents the authority component of a URI.
@@ -69,7 +69,7 @@ impl Authority {
     // Note: this may return an *empty* Authority. You might want `parse_non_empty`.
     // Postcondition: for all Ok() returns, s[..ret.unwrap()] is valid UTF-8 where
     // ret is the return value.
-    pub(super) fn parse(s: &[u8]) -> Result<usize, InvalidUri> {
+    pub(super) const fn parse(s: &[u8]) -> Result<usize, InvalidUri> {
         const MAX_COLONS: u32 = 8; // e.g., [FEDC:BA98:7654:3210:FEDC:BA98:7654:3210]:80
 
         let mut colon_cnt = 0u32;
@@ -83,7 +83,9 @@ impl Authority {
         // first '/', '?', or '#' is a valid URI character (or in some contexts,
         // a '%'). This means that each such byte is a valid single-byte UTF-8
         // code point.
-        for (i, &b) in s.iter().enumerate() {
+        let mut i = 0;
+        while i < s.len() {
+            let b = s[i];
             match URI_CHARS[b as usize] {
                 b'/' | b'?' | b'#' => {
                     end = i;
@@ -91,20 +93,20 @@ impl Authority {
                 }
                 b':' => {
                     if colon_cnt >= MAX_COLONS {
-                        return Err(ErrorKind::InvalidAuthority.into());
+                        return Err(InvalidUri(ErrorKind::InvalidAuthority));
                     }
                     colon_cnt += 1;
                 }
                 b'[' => {
                     if has_percent || start_bracket {
                         // Something other than the userinfo has a `%`, so reject it.
-                        return Err(ErrorKind::InvalidAuthority.into());
+                        return Err(InvalidUri(ErrorKind::InvalidAuthority));
                     }
                     start_bracket = true;
                 }
                 b']' => {
                     if (!start_bracket) || end_bracket {
-                        return Err(ErrorKind::InvalidAuthority.into());
+                        return Err(InvalidUri(ErrorKind::InvalidAuthority));
                     }
                     end_bracket = true;
 
@@ -134,29 +136,30 @@ impl Authority {
                     has_percent = true;
                 }
                 0 => {
-                    return Err(ErrorKind::InvalidUriChar.into());
+                    return Err(InvalidUri(ErrorKind::InvalidUriChar));
                 }
                 _ => {}
             }
+            i += 1;
         }
 
         if start_bracket ^ end_bracket {
-            return Err(ErrorKind::InvalidAuthority.into());
+            return Err(InvalidUri(ErrorKind::InvalidAuthority));
         }
 
         if colon_cnt > 1 {
             // Things like 'localhost:8080:3030' are rejected.
-            return Err(ErrorKind::InvalidAuthority.into());
+            return Err(InvalidUri(ErrorKind::InvalidAuthority));
         }
 
-        if end > 0 && at_sign_pos == Some(end - 1) {
+        if end > 0 && matches!(at_sign_pos, Some(pos) if pos == end - 1) {
             // If there's nothing after an `@`, this is bonkers.
-            return Err(ErrorKind::InvalidAuthority.into());
+            return Err(InvalidUri(ErrorKind::InvalidAuthority));
         }
 
         if has_percent {
             // Something after the userinfo has a `%`, so reject it.
-            return Err(ErrorKind::InvalidAuthority.into());
+            return Err(InvalidUri(ErrorKind::InvalidAuthority));
         }
 
         Ok(end)
@@ -169,9 +172,9 @@ impl Authority {
     //
     // Postcondition: for all Ok() returns, s[..ret.unwrap()] is valid UTF-8 where
     // ret is the return value.
-    fn parse_non_empty(s: &[u8]) -> Result<usize, InvalidUri> {
+    pub(super) const fn parse_non_empty(s: &[u8]) -> Result<usize, InvalidUri> {
         if s.is_empty() {
-            return Err(ErrorKind::Empty.into());
+            return Err(InvalidUri(ErrorKind::Empty));
         }
         Self::parse(s)
     }
@@ -258,6 +261,32 @@ impl Authority {
         self.port().map(|p| p.as_u16())
     }
 
+    /// Returns true if this `Authority` refers to the same host and port as
+    /// `other` when used with `scheme`.
+    ///
+    /// Hosts are compared case-insensitively. When an authority has no
+    /// explicit port, the default port of `scheme` is used instead, so
+    /// `example.com` and `example.com:80` are equivalent for `http`. Any user
+    /// information is ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::uri::*;
+    /// let a: Authority = "example.com".parse().unwrap();
+    /// let b: Authority = "EXAMPLE.com:80".parse().unwrap();
+    ///
+    /// assert!(a.equivalent_to(&b, &Scheme::HTTP));
+    /// assert!(!a.equivalent_to(&b, &Scheme::HTTPS));
+    /// ```
+    #[must_use]
+    pub fn equivalent_to(&self, other: &Self, scheme: &Scheme) -> bool {
+        let default_port = scheme.default_port();
+
+        self.host().eq_ignore_ascii_case(other.host())
+            && self.port_u16().or(default_port) == other.port_u16().or(default_port)
+    }
+
     /// Return a str representation of the authority
     #[inline]
     pub fn as_str(&self) -> &str {
@@ -340,9 +369,34 @@ impl PartialEq<Authority> for String {
 /// ```
 impl PartialOrd for Authority {
     fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Case-insensitive ordering, consistent with `Eq` and `Hash`
+///
+/// Authorities are ordered by their bytes with ASCII letters lowercased, so
+/// they can be kept in a `BTreeSet` or `BTreeMap`.
+///
+/// # Examples
+///
+/// ```
+/// # use http::uri::Authority;
+/// use std::collections::BTreeSet;
+///
+/// let backends: BTreeSet<Authority> = ["b.example:80", "A.example:80", "a.example:80"]
+///     .into_iter()
+///     .map(|s| s.parse().unwrap())
+///     .collect();
+/// assert_eq!(backends.len(), 2);
+/// assert_eq!(backends.first().unwrap(), "a.example:80");
+/// assert_eq!(backends.last().unwrap(), "b.example:80");
+/// ```
+impl Ord for Authority {
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
         let left = self.data.as_bytes().iter().map(u8::to_ascii_lowercase);
         let right = other.data.as_bytes().iter().map(u8::to_ascii_lowercase);
-        left.partial_cmp(right)
+        left.cmp(right)
     }
 }
 
@@ -683,4 +737,75 @@ mod tests {
         let err = Authority::parse_non_empty(b"]o[").unwrap_err();
         assert_eq!(err.0, ErrorKind::InvalidAuthority);
     }
+
+    #[test]
+    fn ord_and_hash_agree_with_eq() {
+        use std::collections::hash_map::DefaultHasher;
+        use std::collections::{BTreeSet, HashMap};
+
+        fn hash(authority: &Authority) -> u64 {
+            let mut hasher = DefaultHasher::new();
+            authority.hash(&mut hasher);
+            hasher.finish()
+        }
+
+        let authorities: Vec<Authority> = [
+            "example.com",
+            "EXAMPLE.com",
+            "example.com:80",
+            "example.com:8080",
+            "user@example.com",
+            "example.org",
+            "[::1]:443",
+        ]
+        .iter()
+        .map(|s| s.parse().unwrap())
+        .collect();
+
+        for a in &authorities {
+            for b in &authorities {
+                assert_eq!(a == b, a.cmp(b) == cmp::Ordering::Equal, "{a} {b}");
+                assert_eq!(a.partial_cmp(b), Some(a.cmp(b)), "{a} {b}");
+                if a == b {
+                    assert_eq!(hash(a), hash(b), "{a} {b}");
+                }
+            }
+        }
+
+        let set: BTreeSet<_> = authorities.iter().cloned().collect();
+        assert_eq!(set.len(), authorities.len() - 1);
+
+        let mut counts = HashMap::new();
+        for authority in &authorities {
+            *counts.entry(authority.clone()).or_insert(0) += 1;
+        }
+        assert_eq!(counts[&authorities[0]], 2);
+    }
+
+    #[test]
+    fn equivalent_to_uses_default_port() {
+        let cases = [
+            ("example.com", "example.com:80", Scheme::HTTP, true),
+            ("example.com", "example.com:443", Scheme::HTTP, false),
+            ("example.com", "example.com:443", Scheme::HTTPS, true),
+            ("Example.COM:8080", "example.com:8080", Scheme::HTTP, true),
+            ("example.com:8080", "example.com", Scheme::HTTP, false),
+            ("user@example.com", "example.com:80", Scheme::HTTP, true),
+            ("example.com", "example.org", Scheme::HTTP, false),
+            ("[::1]", "[::1]:443", Scheme::HTTPS, true),
+        ];
+
+        for (a, b, scheme, expected) in cases {
+            let a: Authority = a.parse().unwrap();
+            let b: Authority = b.parse().unwrap();
+            assert_eq!(a.equivalent_to(&b, &scheme), expected, "{a} {b}");
+            assert_eq!(b.equivalent_to(&a, &scheme), expected, "{b} {a}");
+        }
+
+        let other: Scheme = "ftp".parse().unwrap();
+        let a: Authority = "example.com".parse().unwrap();
+        let b: Authority = "example.com:21".parse().unwrap();
+        assert!(!a.equivalent_to(&b, &other));
+        assert!(a.equivalent_to(&a, &other));
+    }
 }
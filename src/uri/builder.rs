@@ -121,7 +121,20 @@ impl Builder {
     ///
     /// Additionally, the various forms of URI require certain combinations of
     /// parts to be set to be valid. If the parts don't fit into any of the
-    /// valid forms of URI, a new error is returned.
+    /// valid forms of URI, a new error is returned. The accepted combinations
+    /// are:
+    ///
+    /// * scheme, authority and path: an absolute URI, such as
+    ///   `http://example.com/`.
+    /// * authority only: the authority form used by `CONNECT`, such as
+    ///   `example.com:443`.
+    /// * path only: an origin form relative reference, such as `/index.html`.
+    /// * nothing set: an empty URI with no components.
+    ///
+    /// A scheme without an authority or path is rejected, and so is an
+    /// authority combined with a path but no scheme. The latter would be a
+    /// network-path reference (`//example.com/`), which can't be used as an
+    /// HTTP request target and isn't represented by `Uri`.
     ///
     /// # Examples
     ///
@@ -212,4 +225,67 @@ mod tests {
         let uri = Builder::from(original_uri.clone()).build().unwrap();
         assert_eq!(original_uri, uri);
     }
+
+    #[test]
+    fn build_from_parts() {
+        let mut parts = Uri::from_static("http://example.com/a?b").into_parts();
+        let scheme = parts.take_scheme().unwrap();
+        assert_eq!(scheme, Scheme::HTTP);
+        assert!(parts.take_scheme().is_none());
+
+        let uri = parts
+            .apply_to(Builder::new().scheme("https"))
+            .build()
+            .unwrap();
+        assert_eq!(uri, "https://example.com/a?b");
+
+        let mut parts = Uri::from_static("http://example.com/a?b").into_parts();
+        assert_eq!(parts.take_authority().unwrap(), "example.com");
+        assert_eq!(parts.take_path_and_query().unwrap(), "/a?b");
+        let uri = parts
+            .apply_to(Builder::new().authority("hyper.rs").path_and_query("/"))
+            .build()
+            .unwrap();
+        assert_eq!(uri, "http://hyper.rs/");
+
+        // Existing builder errors are kept.
+        let parts = Uri::from_static("/").into_parts();
+        assert!(parts.apply_to(Builder::new().scheme("")).build().is_err());
+    }
+
+    #[test]
+    fn build_rejects_invalid_combinations() {
+        fn err(builder: Builder) -> String {
+            builder.build().unwrap_err().get_ref().to_string()
+        }
+
+        assert_eq!(err(Builder::new().scheme("http")), "authority missing");
+        assert_eq!(
+            err(Builder::new().scheme("http").path_and_query("/")),
+            "authority missing"
+        );
+        assert_eq!(
+            err(Builder::new().scheme("https").authority("example.com")),
+            "path missing"
+        );
+        assert_eq!(
+            err(Builder::new().authority("example.com").path_and_query("/")),
+            "scheme missing"
+        );
+        assert!(
+            Builder::new()
+                .scheme("http")
+                .path_and_query("not a valid path")
+                .build()
+                .unwrap_err()
+                .is_invalid_uri()
+        );
+
+        let uri = Builder::new().authority("example.com:443").build().unwrap();
+        assert_eq!(uri, "example.com:443");
+        assert!(uri.scheme().is_none());
+        assert_eq!(uri.path(), "");
+
+        assert_eq!(Builder::new().build().unwrap().path(), "");
+    }
 }
@@ -1,3 +1,4 @@
+use std::borrow::Cow;
 use std::convert::TryFrom;
 use std::str::FromStr;
 use std::{cmp, fmt, hash, str};
@@ -19,102 +20,133 @@ const NONE: u16 = u16::MAX;
 impl PathAndQuery {
     // Not public while `bytes` is unstable.
     pub(super) fn from_shared(mut src: Bytes) -> Result<Self, InvalidUri> {
+        let (query, end) = Self::scan(&src)?;
+
+        src.truncate(end);
+
+        // Safety: scan() ensures that src[..end] is valid UTF-8.
+        let data = unsafe { ByteStr::from_utf8_unchecked(src) };
+
+        Ok(Self { data, query })
+    }
+
+    /// Validates a path and query, returning the position of the `?` (or
+    /// `NONE`) and where the value ends, which is before any fragment.
+    ///
+    /// Postcondition: for all `Ok` returns, `src[..end]` is valid UTF-8.
+    pub(super) const fn scan(src: &[u8]) -> Result<(u16, usize), InvalidUri> {
         let mut query = NONE;
-        let mut fragment = None;
+        let mut end = src.len();
 
         let mut is_maybe_not_utf8 = false;
 
-        // block for iterator borrow
-        {
-            let mut iter = src.as_ref().iter().enumerate();
+        let mut i = 0;
 
-            // path ...
-            for (i, &b) in &mut iter {
-                // See https://url.spec.whatwg.org/#path-state
-                match b {
-                    b'?' => {
-                        debug_assert_eq!(query, NONE);
-                        query = i as u16;
-                        break;
-                    }
-                    b'#' => {
-                        fragment = Some(i);
-                        break;
-                    }
+        // path ...
+        while i < src.len() {
+            let b = src[i];
+            i += 1;
+
+            // See https://url.spec.whatwg.org/#path-state
+            match b {
+                b'?' => {
+                    query = (i - 1) as u16;
+                    break;
+                }
+                b'#' => {
+                    end = i - 1;
+                    break;
+                }
+
+                // This is the range of bytes that don't need to be
+                // percent-encoded in the path. If it should have been
+                // percent-encoded, then error.
+                #[rustfmt::skip]
+                0x21 |
+                0x24..=0x3B |
+                0x3D |
+                0x40..=0x5F |
+                0x61..=0x7A |
+                0x7C |
+                0x7E | b'"' |
+                b'{' | b'}' => {}
+
+                // potentially utf8, might not, should check
+                0x7F..=0xFF => {
+                    is_maybe_not_utf8 = true;
+                }
 
-                    // This is the range of bytes that don't need to be
-                    // percent-encoded in the path. If it should have been
-                    // percent-encoded, then error.
+                // These are code points that are supposed to be
+                // percent-encoded in the path but there are clients
+                // out there sending them as is and httparse accepts
+                // to parse those requests, so they are allowed here
+                // for parity.
+                //
+                // For reference, those are code points that are used
+                // to send requests with JSON directly embedded in
+                // the URI path. Yes, those things happen for real.
+                #[rustfmt::skip]
+                _ => return Err(InvalidUri(ErrorKind::InvalidUriChar)),
+            }
+        }
+
+        // query ...
+        if query != NONE {
+            while i < src.len() {
+                let b = src[i];
+                i += 1;
+
+                match b {
+                    // While queries *should* be percent-encoded, most
+                    // bytes are actually allowed...
+                    // See https://url.spec.whatwg.org/#query-state
+                    //
+                    // Allowed: 0x21 / 0x24 - 0x3B / 0x3D / 0x3F - 0x7E
                     #[rustfmt::skip]
                     0x21 |
                     0x24..=0x3B |
                     0x3D |
-                    0x40..=0x5F |
-                    0x61..=0x7A |
-                    0x7C |
-                    0x7E | b'"' |
-                    b'{' | b'}' => {}
+                    0x3F..=0x7E => {}
 
-                    // potentially utf8, might not, should check
                     0x7F..=0xFF => {
                         is_maybe_not_utf8 = true;
                     }
 
-                    // These are code points that are supposed to be
-                    // percent-encoded in the path but there are clients
-                    // out there sending them as is and httparse accepts
-                    // to parse those requests, so they are allowed here
-                    // for parity.
-                    //
-                    // For reference, those are code points that are used
-                    // to send requests with JSON directly embedded in
-                    // the URI path. Yes, those things happen for real.
-                    #[rustfmt::skip]
-                    _ => return Err(ErrorKind::InvalidUriChar.into()),
-                }
-            }
-
-            // query ...
-            if query != NONE {
-                for (i, &b) in iter {
-                    match b {
-                        // While queries *should* be percent-encoded, most
-                        // bytes are actually allowed...
-                        // See https://url.spec.whatwg.org/#query-state
-                        //
-                        // Allowed: 0x21 / 0x24 - 0x3B / 0x3D / 0x3F - 0x7E
-                        #[rustfmt::skip]
-                        0x21 |
-                        0x24..=0x3B |
-                        0x3D |
-                        0x3F..=0x7E => {}
-
-                        0x7F..=0xFF => {
-                            is_maybe_not_utf8 = true;
-                        }
-
-                        b'#' => {
-                            fragment = Some(i);
-                            break;
-                        }
-
-                        _ => return Err(ErrorKind::InvalidUriChar.into()),
+                    b'#' => {
+                        end = i - 1;
+                        break;
                     }
+
+                    _ => return Err(InvalidUri(ErrorKind::InvalidUriChar)),
                 }
             }
         }
 
-        if let Some(i) = fragment {
-            src.truncate(i);
+        if is_maybe_not_utf8 && str::from_utf8(src.split_at(end).0).is_err() {
+            return Err(InvalidUri(ErrorKind::InvalidUriChar));
         }
 
-        let data = if is_maybe_not_utf8 {
-            ByteStr::from_utf8(src).map_err(|_| ErrorKind::InvalidUriChar)?
-        } else {
-            unsafe { ByteStr::from_utf8_unchecked(src) }
-        };
+        Ok((query, end))
+    }
 
-        Ok(Self { data, query })
+    /// Assembles a `PathAndQuery` from a separate path and query, reusing the
+    /// path's buffer.
+    ///
+    /// The path may not contain `?` or `#`, and the query may not contain
+    /// `#`, since either would change where the components are split.
+    fn from_components(mut path: Vec<u8>, query: Option<&[u8]>) -> Result<Self, InvalidUri> {
+        if path.iter().any(|&b| b == b'?' || b == b'#') || query.is_some_and(|q| q.contains(&b'#'))
+        {
+            return Err(ErrorKind::InvalidUriChar.into());
+        }
+
+        if let Some(query) = query {
+            path.reserve(query.len() + 1);
+            path.push(b'?');
+            path.extend_from_slice(query);
+        }
+
+        Self::from_shared(path.into())
     }
 
     /// Convert a `PathAndQuery` from a static string.
@@ -231,6 +263,11 @@ impl PathAndQuery {
     ///                                                           query
     /// ```
     ///
+    /// This never fails: a `PathAndQuery` containing bytes that aren't valid
+    /// UTF-8 is rejected when parsed. Percent-encoded sequences are returned
+    /// as is though, and may not decode to valid UTF-8; see
+    /// [`query_raw`](Self::query_raw) for byte-oriented decoding.
+    ///
     /// # Examples
     ///
     /// With a query string component
@@ -260,6 +297,129 @@ impl PathAndQuery {
         }
     }
 
+    /// Returns the query string component as bytes.
+    ///
+    /// This is the same as [`query`](Self::query), for use with decoders that
+    /// work on bytes, such as percent-decoders that must handle encoded bytes
+    /// which aren't valid UTF-8.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::uri::*;
+    /// let path_and_query: PathAndQuery = "/search?q=%FF".parse().unwrap();
+    ///
+    /// assert_eq!(path_and_query.query_raw(), Some(&b"q=%FF"[..]));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn query_raw(&self) -> Option<&[u8]> {
+        self.query().map(str::as_bytes)
+    }
+
+    /// Returns an iterator over the decoded key-value pairs of the query.
+    ///
+    /// The query is split on `&`, and each pair on its first `=`. Keys and
+    /// values are decoded as `application/x-www-form-urlencoded`:
+    ///
+    /// * `+` decodes to a space, and `%XX` to the byte it encodes. A `%` that
+    ///   isn't followed by two hex digits is kept as-is.
+    /// * Decoded bytes that aren't valid UTF-8 are replaced with `U+FFFD`.
+    /// * Keys and values are only allocated if decoding changes them.
+    ///
+    /// A pair without `=` has an empty value, and `=value` has an empty key.
+    /// Empty pairs, such as in `a=1&&b=2`, are skipped, so an empty or missing
+    /// query yields nothing. Repeated keys are all returned, in order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::uri::*;
+    /// let path_and_query: PathAndQuery = "/search?q=rust+http&tag=a&tag=b%26c&flag".parse().unwrap();
+    /// let params: Vec<_> = path_and_query.iter_query_params().collect();
+    ///
+    /// assert_eq!(
+    ///     params,
+    ///     [
+    ///         ("q".into(), "rust http".into()),
+    ///         ("tag".into(), "a".into()),
+    ///         ("tag".into(), "b&c".into()),
+    ///         ("flag".into(), "".into()),
+    ///     ]
+    /// );
+    /// ```
+    pub fn iter_query_params(&self) -> impl Iterator<Item = (Cow<'_, str>, Cow<'_, str>)> {
+        self.query()
+            .unwrap_or("")
+            .split('&')
+            .filter(|pair| !pair.is_empty())
+            .map(|pair| {
+                let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+                (form_decode(key), form_decode(value))
+            })
+    }
+
+    /// Returns the decoded value of the first query parameter named `key`.
+    ///
+    /// `key` is compared with the decoded keys of
+    /// [`iter_query_params`](Self::iter_query_params).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::uri::*;
+    /// let path_and_query: PathAndQuery = "/search?q=caf%C3%A9&page=2&page=3".parse().unwrap();
+    ///
+    /// assert_eq!(path_and_query.query_param("q").unwrap(), "café");
+    /// assert_eq!(path_and_query.query_param("page").unwrap(), "2");
+    /// assert!(path_and_query.query_param("missing").is_none());
+    /// ```
+    #[must_use]
+    pub fn query_param(&self, key: &str) -> Option<Cow<'_, str>> {
+        self.iter_query_params()
+            .find(|(k, _)| k == key)
+            .map(|(_, value)| value)
+    }
+
+    /// Returns true if a query string component is present.
+    ///
+    /// An empty query, as in `/hello?`, counts as present.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::uri::*;
+    /// let path_and_query: PathAndQuery = "/hello/world?key=value".parse().unwrap();
+    /// assert!(path_and_query.has_query());
+    ///
+    /// let path_and_query: PathAndQuery = "/hello/world".parse().unwrap();
+    /// assert!(!path_and_query.has_query());
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn has_query(&self) -> bool {
+        self.query != NONE
+    }
+
+    /// Returns true if the path component is anything other than the root
+    /// path `/`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::uri::*;
+    /// let path_and_query: PathAndQuery = "/hello/world".parse().unwrap();
+    /// assert!(path_and_query.has_path());
+    ///
+    /// let path_and_query: PathAndQuery = "/?key=value".parse().unwrap();
+    /// assert!(!path_and_query.has_path());
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn has_path(&self) -> bool {
+        self.path() != "/"
+    }
+
     /// Returns the path and query as a string component.
     ///
     /// # Examples
@@ -331,6 +491,49 @@ impl TryFrom<&String> for PathAndQuery {
     }
 }
 
+/// Assembles a `PathAndQuery` from a path and an optional query.
+///
+/// The query is given without its leading `?`. An error is returned if either
+/// component contains invalid characters, or characters such as `?` in the
+/// path that would move the boundary between the two.
+///
+/// # Examples
+///
+/// ```
+/// # use http::uri::PathAndQuery;
+/// let pq = PathAndQuery::try_from(("/search".to_string(), Some("q=rust".to_string()))).unwrap();
+/// assert_eq!(pq, "/search?q=rust");
+///
+/// assert!(PathAndQuery::try_from(("/a?b".to_string(), None)).is_err());
+/// ```
+impl TryFrom<(String, Option<String>)> for PathAndQuery {
+    type Error = InvalidUri;
+    #[inline]
+    fn try_from((path, query): (String, Option<String>)) -> Result<Self, Self::Error> {
+        Self::from_components(path.into_bytes(), query.as_deref().map(str::as_bytes))
+    }
+}
+
+/// Assembles a `PathAndQuery` from a path and an optional query.
+///
+/// This behaves like the `(String, Option<String>)` conversion.
+///
+/// # Examples
+///
+/// ```
+/// # use http::uri::PathAndQuery;
+/// let pq = PathAndQuery::try_from(("/search", Some("q=rust"))).unwrap();
+/// assert_eq!(pq.path(), "/search");
+/// assert_eq!(pq.query(), Some("q=rust"));
+/// ```
+impl<'a> TryFrom<(&'a str, Option<&'a str>)> for PathAndQuery {
+    type Error = InvalidUri;
+    #[inline]
+    fn try_from((path, query): (&'a str, Option<&'a str>)) -> Result<Self, Self::Error> {
+        Self::from_components(path.as_bytes().to_vec(), query.map(str::as_bytes))
+    }
+}
+
 impl FromStr for PathAndQuery {
     type Err = InvalidUri;
     #[inline]
@@ -466,6 +669,40 @@ impl PartialOrd<PathAndQuery> for String {
     }
 }
 
+/// Decodes one key or value of an `application/x-www-form-urlencoded` query.
+fn form_decode(src: &str) -> Cow<'_, str> {
+    if !src.contains(['%', '+']) {
+        return Cow::Borrowed(src);
+    }
+
+    let hex = |b: u8| char::from(b).to_digit(16);
+    let bytes = src.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' => decoded.push(b' '),
+            b'%' => match (
+                bytes.get(i + 1).and_then(|&b| hex(b)),
+                bytes.get(i + 2).and_then(|&b| hex(b)),
+            ) {
+                (Some(hi), Some(lo)) => {
+                    decoded.push((hi * 16 + lo) as u8);
+                    i += 2;
+                }
+                _ => decoded.push(b'%'),
+            },
+            b => decoded.push(b),
+        }
+        i += 1;
+    }
+
+    match String::from_utf8(decoded) {
+        Ok(decoded) => Cow::Owned(decoded),
+        Err(err) => Cow::Owned(String::from_utf8_lossy(err.as_bytes()).into_owned()),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -579,6 +816,50 @@ mod tests {
         assert_eq!(Some("pizza=🍕"), pq("/test?pizza=🍕").query());
     }
 
+    #[test]
+    fn query_raw() {
+        assert_eq!(
+            pq("/test?pizza=🍕").query_raw(),
+            Some("pizza=🍕".as_bytes())
+        );
+        assert_eq!(pq("/test?a=%C0%AF").query_raw(), Some(&b"a=%C0%AF"[..]));
+        assert_eq!(pq("/test?").query_raw(), Some(&b""[..]));
+        assert_eq!(pq("/test").query_raw(), None);
+    }
+
+    #[test]
+    fn try_from_components() {
+        let pq = PathAndQuery::try_from(("/a/b", Some("c=d&e"))).unwrap();
+        assert_eq!(pq.path(), "/a/b");
+        assert_eq!(pq.query(), Some("c=d&e"));
+
+        let pq = PathAndQuery::try_from(("/a".to_string(), None)).unwrap();
+        assert_eq!(pq, "/a");
+        assert_eq!(pq.query(), None);
+
+        let pq = PathAndQuery::try_from(("/", Some(""))).unwrap();
+        assert_eq!(pq.query(), Some(""));
+
+        // `?` is allowed inside the query itself.
+        let pq = PathAndQuery::try_from(("/a", Some("b?c"))).unwrap();
+        assert_eq!(pq.query(), Some("b?c"));
+
+        for (path, query) in [
+            ("/a?b", None),
+            ("/a#b", None),
+            ("/a", Some("b#c")),
+            ("/a b", None),
+            ("/a", Some("b c")),
+        ] {
+            assert!(
+                PathAndQuery::try_from((path, query)).is_err(),
+                "{path:?} {query:?}"
+            );
+            let owned = (path.to_string(), query.map(str::to_string));
+            assert!(PathAndQuery::try_from(owned).is_err(), "{path:?} {query:?}");
+        }
+    }
+
     #[test]
     fn rejects_invalid_utf8_in_path() {
         PathAndQuery::try_from(&[b'/', 0xFF][..]).expect_err("reject invalid utf8");
@@ -600,4 +881,80 @@ mod tests {
     fn pq(s: &str) -> PathAndQuery {
         s.parse().unwrap_or_else(|_| panic!("parsing {s}"))
     }
+
+    #[test]
+    fn has_query_and_path() {
+        for (src, path, query) in [
+            ("/", false, false),
+            ("/?", false, true),
+            ("/?a=b", false, true),
+            ("?a=b", false, true),
+            ("/a", true, false),
+            ("/a?", true, true),
+            ("*", true, false),
+        ] {
+            let path_and_query: PathAndQuery = src.parse().unwrap();
+            assert_eq!(path_and_query.has_path(), path, "{src}");
+            assert_eq!(path_and_query.has_query(), query, "{src}");
+        }
+    }
+
+    fn params(s: &str) -> Vec<(String, String)> {
+        pq(s)
+            .iter_query_params()
+            .map(|(k, v)| (k.into_owned(), v.into_owned()))
+            .collect()
+    }
+
+    #[test]
+    fn iter_query_params_edge_cases() {
+        let pairs = |list: &[(&str, &str)]| -> Vec<(String, String)> {
+            list.iter()
+                .map(|&(k, v)| (k.to_owned(), v.to_owned()))
+                .collect()
+        };
+
+        assert_eq!(params("/"), pairs(&[]));
+        assert_eq!(params("/?"), pairs(&[]));
+        assert_eq!(params("/?&&"), pairs(&[]));
+        assert_eq!(params("/?key"), pairs(&[("key", "")]));
+        assert_eq!(params("/?=value"), pairs(&[("", "value")]));
+        assert_eq!(params("/?="), pairs(&[("", "")]));
+        assert_eq!(params("/?a=1&a=2"), pairs(&[("a", "1"), ("a", "2")]));
+        assert_eq!(params("/?a=1&&b=2&"), pairs(&[("a", "1"), ("b", "2")]));
+        assert_eq!(params("/?a=b=c"), pairs(&[("a", "b=c")]));
+        assert_eq!(params("/?a+b=c+d"), pairs(&[("a b", "c d")]));
+        assert_eq!(params("/?k%3D=%26v%2B"), pairs(&[("k=", "&v+")]));
+        assert_eq!(
+            params("/?a=%C3%A9%e2%82%ac"),
+            pairs(&[("a", "\u{e9}\u{20ac}")])
+        );
+        assert_eq!(
+            params("/?a=100%&b=%zz&c=%4"),
+            pairs(&[("a", "100%"), ("b", "%zz"), ("c", "%4")])
+        );
+        assert_eq!(params("/?a=%FF"), pairs(&[("a", "\u{fffd}")]));
+        assert_eq!(params("/?a=1#b=2"), pairs(&[("a", "1")]));
+    }
+
+    #[test]
+    fn iter_query_params_borrows_when_unchanged() {
+        let path_and_query = pq("/?plain=value&coded=a%20b");
+        let params: Vec<_> = path_and_query.iter_query_params().collect();
+        assert!(matches!(
+            params[0],
+            (Cow::Borrowed("plain"), Cow::Borrowed("value"))
+        ));
+        assert!(matches!(params[1].1, Cow::Owned(_)));
+    }
+
+    #[test]
+    fn query_param() {
+        let path_and_query = pq("/?a=1&b&a=2&c%20d=e");
+        assert_eq!(path_and_query.query_param("a").unwrap(), "1");
+        assert_eq!(path_and_query.query_param("b").unwrap(), "");
+        assert_eq!(path_and_query.query_param("c d").unwrap(), "e");
+        assert_eq!(path_and_query.query_param("c%20d"), None);
+        assert_eq!(pq("/").query_param("a"), None);
+    }
 }
@@ -66,6 +66,31 @@ impl Scheme {
             None => unreachable!(),
         }
     }
+
+    /// Returns the default port for this scheme, if it is known.
+    ///
+    /// This is `80` for `http` and `443` for `https`, and `None` for any
+    /// other scheme.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::uri::*;
+    /// assert_eq!(Scheme::HTTP.default_port(), Some(80));
+    /// assert_eq!(Scheme::HTTPS.default_port(), Some(443));
+    ///
+    /// let scheme: Scheme = "ftp".parse().unwrap();
+    /// assert_eq!(scheme.default_port(), None);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn default_port(&self) -> Option<u16> {
+        match self.inner {
+            Scheme2::Standard(Protocol::Http) => Some(80),
+            Scheme2::Standard(Protocol::Https) => Some(443),
+            Scheme2::Other(_) | Scheme2::None => None,
+        }
+    }
 }
 
 impl<'a> TryFrom<&'a [u8]> for Scheme {
@@ -163,6 +188,28 @@ impl PartialEq<Scheme> for str {
     }
 }
 
+/// Case-insensitive equality
+///
+/// # Examples
+///
+/// ```
+/// # use http::uri::Scheme;
+/// assert_eq!(Scheme::HTTP, "http");
+/// assert_eq!("HTTPS", Scheme::HTTPS);
+/// ```
+impl PartialEq<&str> for Scheme {
+    fn eq(&self, other: &&str) -> bool {
+        self == *other
+    }
+}
+
+/// Case-insensitive equality
+impl PartialEq<Scheme> for &str {
+    fn eq(&self, other: &Scheme) -> bool {
+        other == *self
+    }
+}
+
 /// Case-insensitive hashing
 impl Hash for Scheme {
     fn hash<H>(&self, state: &mut H)
@@ -259,24 +306,21 @@ impl Scheme2<usize> {
         }
     }
 
-    pub(super) fn parse(s: &[u8]) -> Result<Self, InvalidUri> {
-        if s.len() >= 7 {
-            // Check for HTTP
-            if s[..7].eq_ignore_ascii_case(b"http://") {
-                // Prefix will be striped
-                return Ok(Protocol::Http.into());
-            }
+    pub(super) const fn parse(s: &[u8]) -> Result<Self, InvalidUri> {
+        // Check for HTTP
+        if starts_with_ignore_ascii_case(s, b"http://") {
+            // Prefix will be striped
+            return Ok(Self::Standard(Protocol::Http));
         }
 
-        if s.len() >= 8 {
-            // Check for HTTPs
-            if s[..8].eq_ignore_ascii_case(b"https://") {
-                return Ok(Protocol::Https.into());
-            }
+        // Check for HTTPs
+        if starts_with_ignore_ascii_case(s, b"https://") {
+            return Ok(Self::Standard(Protocol::Https));
         }
 
         if s.len() > 3 {
-            for i in 0..s.len() {
+            let mut i = 0;
+            while i < s.len() {
                 let b = s[i];
 
                 match SCHEME_CHARS[b as usize] {
@@ -287,12 +331,12 @@ impl Scheme2<usize> {
                         }
 
                         // Not a scheme
-                        if &s[i + 1..i + 3] != b"//" {
+                        if s[i + 1] != b'/' || s[i + 2] != b'/' {
                             break;
                         }
 
                         if i > MAX_SCHEME_LEN {
-                            return Err(ErrorKind::SchemeTooLong.into());
+                            return Err(InvalidUri(ErrorKind::SchemeTooLong));
                         }
 
                         // Return scheme
@@ -302,6 +346,8 @@ impl Scheme2<usize> {
                     0 => break,
                     _ => {}
                 }
+
+                i += 1;
             }
         }
 
@@ -309,6 +355,21 @@ impl Scheme2<usize> {
     }
 }
 
+const fn starts_with_ignore_ascii_case(s: &[u8], prefix: &[u8]) -> bool {
+    if s.len() < prefix.len() {
+        return false;
+    }
+
+    let mut i = 0;
+    while i < prefix.len() {
+        if !s[i].eq_ignore_ascii_case(&prefix[i]) {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
 impl Protocol {
     pub(super) const fn len(self) -> usize {
         match self {
@@ -343,6 +404,15 @@ mod test {
         assert_eq!(&scheme("my+funky+scheme"), "my+funky+scheme");
     }
 
+    #[test]
+    fn scheme_eq_to_str_ref() {
+        assert_eq!(Scheme::HTTP, "http");
+        assert_eq!("HTTP", Scheme::HTTP);
+        assert_eq!(scheme("ftp"), "FTP");
+        assert_ne!(Scheme::HTTPS, "http");
+        assert_ne!("http", Scheme::HTTPS);
+    }
+
     #[test]
     fn invalid_scheme_is_error() {
         Scheme::try_from("my_funky_scheme").expect_err("Unexpectedly valid Scheme");
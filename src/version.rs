@@ -16,13 +16,25 @@
 //! let http2 = Version::HTTP_2;
 //! assert!(http11 != http2);
 //!
-//! println!("{:?}", http2);
+//! println!("{}", http2);
+//! assert_eq!("HTTP/1.1".parse::<Version>().unwrap(), http11);
 //! ```
 
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+use std::error::Error;
 use std::fmt;
+use std::str::FromStr;
 
 /// Represents a version of the HTTP spec.
-#[derive(PartialEq, PartialOrd, Copy, Clone, Eq, Ord, Hash)]
+///
+/// Versions are ordered chronologically: an older version always compares
+/// less than a newer one, so `version >= Version::HTTP_11` can be relied on.
+/// See also [`is_at_least`](Self::is_at_least).
+///
+/// Besides the constants, a `Version` can hold any other `HTTP/x.y` version,
+/// see [`Version::other`].
+#[derive(PartialEq, Copy, Clone, Eq, Hash)]
 pub struct Version(Http);
 
 impl Version {
@@ -30,6 +42,242 @@ impl Version {
     pub const fn new() -> Self {
         Self::HTTP_11
     }
+
+    /// Returns the version as it appears in an HTTP/1 request or status
+    /// line, such as `HTTP/1.1`.
+    ///
+    /// HTTP/2 and HTTP/3 are rendered as `HTTP/2.0` and `HTTP/3.0`, and
+    /// other versions as `HTTP/{major}.{minor}`. This is also the output of
+    /// the `Display` and `Debug` implementations, and is accepted by the
+    /// `FromStr` implementation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::Version;
+    /// assert_eq!(Version::HTTP_11.as_str(), "HTTP/1.1");
+    /// assert_eq!(Version::HTTP_2.as_str(), "HTTP/2.0");
+    /// ```
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        use self::Http::{__NonExhaustive, H2, H3, Http09, Http10, Http11, Other};
+
+        match self.0 {
+            Http09 => "HTTP/0.9",
+            Http10 => "HTTP/1.0",
+            Http11 => "HTTP/1.1",
+            H2 => "HTTP/2.0",
+            H3 => "HTTP/3.0",
+            Other(major, minor) => {
+                let bytes = &OTHER_VERSION_STRS[(major * 10 + minor) as usize];
+                match std::str::from_utf8(bytes) {
+                    Ok(s) => s,
+                    Err(_) => unreachable!(),
+                }
+            }
+            __NonExhaustive => unreachable!(),
+        }
+    }
+
+    /// Creates a `Version` from any `major.minor` pair of digits, such as
+    /// `HTTP/1.2`, which has no constant.
+    ///
+    /// This is meant for tools that need to represent versions exactly as
+    /// they were received. Pairs matching a constant return that constant,
+    /// so `Version::other(1, 1)` is equal to `Version::HTTP_11`, and
+    /// versions compare by their numbers.
+    ///
+    /// An error is returned if either number is greater than 9, since an
+    /// HTTP version is written with a single digit on each side of the dot.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::Version;
+    /// let version = Version::other(1, 2).unwrap();
+    /// assert_eq!(version.as_str(), "HTTP/1.2");
+    /// assert!(version > Version::HTTP_11 && version < Version::HTTP_2);
+    ///
+    /// assert_eq!(Version::other(2, 0).unwrap(), Version::HTTP_2);
+    /// assert!(Version::other(10, 0).is_err());
+    /// ```
+    pub const fn other(major: u8, minor: u8) -> Result<Self, InvalidVersion> {
+        if major > 9 || minor > 9 {
+            return Err(InvalidVersion { _priv: () });
+        }
+
+        Ok(match (major, minor) {
+            (0, 9) => Self::HTTP_09,
+            (1, 0) => Self::HTTP_10,
+            (1, 1) => Self::HTTP_11,
+            (2, 0) => Self::HTTP_2,
+            (3, 0) => Self::HTTP_3,
+            _ => Self(Http::Other(major, minor)),
+        })
+    }
+
+    /// Returns the major version number, such as `1` for `HTTP/1.1` or `2`
+    /// for `HTTP/2`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::Version;
+    /// assert_eq!(Version::HTTP_11.major(), 1);
+    /// assert!(Version::HTTP_3.major() >= 2);
+    /// ```
+    #[must_use]
+    pub const fn major(self) -> u8 {
+        self.numbers().0
+    }
+
+    /// Returns the minor version number, such as `1` for `HTTP/1.1`.
+    ///
+    /// HTTP/2 and HTTP/3 have no minor version, and report `0`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::Version;
+    /// assert_eq!(Version::HTTP_11.minor(), 1);
+    /// assert_eq!(Version::HTTP_2.minor(), 0);
+    /// ```
+    #[must_use]
+    pub const fn minor(self) -> u8 {
+        self.numbers().1
+    }
+
+    /// Returns true if this version is `other` or a newer version.
+    ///
+    /// This is the same as `self >= other`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::Version;
+    /// assert!(Version::HTTP_2.is_at_least(Version::HTTP_11));
+    /// assert!(Version::HTTP_11.is_at_least(Version::HTTP_11));
+    /// assert!(!Version::HTTP_10.is_at_least(Version::HTTP_11));
+    /// ```
+    #[must_use]
+    pub const fn is_at_least(self, other: Self) -> bool {
+        let (major, minor) = self.numbers();
+        let (other_major, other_minor) = other.numbers();
+        major > other_major || (major == other_major && minor >= other_minor)
+    }
+
+    /// Returns true if this version can multiplex several requests over a
+    /// single connection, which is the case from HTTP/2 onwards.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::Version;
+    /// assert!(Version::HTTP_2.supports_multiplexing());
+    /// assert!(Version::HTTP_3.supports_multiplexing());
+    /// assert!(!Version::HTTP_11.supports_multiplexing());
+    /// ```
+    #[must_use]
+    pub const fn supports_multiplexing(self) -> bool {
+        self.major() >= 2
+    }
+
+    /// Returns true if this is `HTTP/2.0`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::Version;
+    /// assert!(Version::HTTP_2.is_http2());
+    /// assert!(!Version::HTTP_11.is_http2());
+    /// ```
+    #[must_use]
+    pub const fn is_http2(self) -> bool {
+        matches!(self.0, Http::H2)
+    }
+
+    /// Returns true if this is `HTTP/3.0`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::Version;
+    /// assert!(Version::HTTP_3.is_http3());
+    /// assert!(!Version::HTTP_2.is_http3());
+    /// ```
+    #[must_use]
+    pub const fn is_http3(self) -> bool {
+        matches!(self.0, Http::H3)
+    }
+
+    /// Returns the version negotiated by an ALPN protocol identifier, as
+    /// registered in the IANA "TLS Application-Layer Protocol Negotiation
+    /// (ALPN) Protocol IDs" registry.
+    ///
+    /// The identifiers are case-sensitive and must match exactly.
+    ///
+    /// `h2c` is not accepted: it identifies HTTP/2 over cleartext TCP, which
+    /// is never negotiated with ALPN, so receiving it during a TLS handshake
+    /// is a peer error rather than a request for HTTP/2.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::Version;
+    /// assert_eq!(Version::from_alpn(b"h2"), Some(Version::HTTP_2));
+    /// assert_eq!(Version::from_alpn(b"http/1.1"), Some(Version::HTTP_11));
+    /// assert_eq!(Version::from_alpn(b"H2"), None);
+    /// ```
+    #[must_use]
+    pub const fn from_alpn(proto: &[u8]) -> Option<Self> {
+        match proto {
+            b"http/1.0" => Some(Self::HTTP_10),
+            b"http/1.1" => Some(Self::HTTP_11),
+            b"h2" => Some(Self::HTTP_2),
+            b"h3" => Some(Self::HTTP_3),
+            _ => None,
+        }
+    }
+
+    /// Returns the ALPN protocol identifier for this version.
+    ///
+    /// HTTP/0.9 and versions created with [`Version::other`] have no
+    /// identifier, and return `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use http::Version;
+    /// assert_eq!(Version::HTTP_2.as_alpn(), Some(&b"h2"[..]));
+    /// assert_eq!(Version::HTTP_09.as_alpn(), None);
+    /// ```
+    #[must_use]
+    pub const fn as_alpn(self) -> Option<&'static [u8]> {
+        use self::Http::{__NonExhaustive, H2, H3, Http09, Http10, Http11, Other};
+
+        match self.0 {
+            Http09 | Other(..) => None,
+            Http10 => Some(b"http/1.0"),
+            Http11 => Some(b"http/1.1"),
+            H2 => Some(b"h2"),
+            H3 => Some(b"h3"),
+            __NonExhaustive => unreachable!(),
+        }
+    }
+
+    const fn numbers(self) -> (u8, u8) {
+        use self::Http::{__NonExhaustive, H2, H3, Http09, Http10, Http11, Other};
+
+        match self.0 {
+            Http09 => (0, 9),
+            Http10 => (1, 0),
+            Http11 => (1, 1),
+            H2 => (2, 0),
+            H3 => (3, 0),
+            Other(major, minor) => (major, minor),
+            __NonExhaustive => unreachable!(),
+        }
+    }
 }
 
 impl Version {
@@ -49,16 +297,46 @@ impl Version {
     pub const HTTP_3: Self = Self(Http::H3);
 }
 
-#[derive(PartialEq, PartialOrd, Copy, Clone, Eq, Ord, Hash)]
+#[derive(PartialEq, Copy, Clone, Eq, Hash)]
 enum Http {
     Http09,
     Http10,
     Http11,
     H2,
     H3,
+    // Invariant: both numbers are digits, and don't match a known version.
+    Other(u8, u8),
     __NonExhaustive,
 }
 
+// `HTTP/{major}.{minor}` for every pair of digits, indexed by
+// `major * 10 + minor`, so that `as_str` can return a `&'static str`.
+static OTHER_VERSION_STRS: [[u8; 8]; 100] = {
+    let mut strs = [*b"HTTP/0.0"; 100];
+    let mut i = 0;
+    while i < 100 {
+        strs[i][5] = b'0' + (i / 10) as u8;
+        strs[i][7] = b'0' + (i % 10) as u8;
+        i += 1;
+    }
+    strs
+};
+
+impl PartialOrd for Version {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Orders versions by their major, then minor, version number.
+impl Ord for Version {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.numbers().cmp(&other.numbers())
+    }
+}
+
 impl Default for Version {
     #[inline]
     fn default() -> Self {
@@ -68,15 +346,244 @@ impl Default for Version {
 
 impl fmt::Debug for Version {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        use self::Http::{__NonExhaustive, H2, H3, Http09, Http10, Http11};
+        f.write_str(self.as_str())
+    }
+}
 
-        f.write_str(match self.0 {
-            Http09 => "HTTP/0.9",
-            Http10 => "HTTP/1.0",
-            Http11 => "HTTP/1.1",
-            H2 => "HTTP/2.0",
-            H3 => "HTTP/3.0",
-            __NonExhaustive => unreachable!(),
-        })
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Converts a `(major, minor)` pair, as returned by [`Version::major`] and
+/// [`Version::minor`], into a `Version`.
+///
+/// Pairs that don't name a known version, such as `(1, 2)`, are rejected;
+/// use [`Version::other`] to accept them.
+///
+/// # Examples
+///
+/// ```
+/// # use http::Version;
+/// # use std::convert::TryFrom;
+/// assert_eq!(Version::try_from((2, 0)).unwrap(), Version::HTTP_2);
+/// assert!(Version::try_from((1, 2)).is_err());
+/// ```
+impl TryFrom<(u8, u8)> for Version {
+    type Error = InvalidVersion;
+
+    fn try_from((major, minor): (u8, u8)) -> Result<Self, Self::Error> {
+        match (major, minor) {
+            (0, 9) => Ok(Self::HTTP_09),
+            (1, 0) => Ok(Self::HTTP_10),
+            (1, 1) => Ok(Self::HTTP_11),
+            (2, 0) => Ok(Self::HTTP_2),
+            (3, 0) => Ok(Self::HTTP_3),
+            _ => Err(InvalidVersion { _priv: () }),
+        }
+    }
+}
+
+/// Parses the strings returned by [`Version::as_str`], that is
+/// `HTTP/{major}.{minor}` with a single digit on each side of the dot.
+///
+/// The comparison is case-sensitive, as HTTP version strings are.
+impl FromStr for Version {
+    type Err = InvalidVersion;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match *s.as_bytes() {
+            [
+                b'H',
+                b'T',
+                b'T',
+                b'P',
+                b'/',
+                major @ b'0'..=b'9',
+                b'.',
+                minor @ b'0'..=b'9',
+            ] => Self::other(major - b'0', minor - b'0'),
+            _ => Err(InvalidVersion { _priv: () }),
+        }
+    }
+}
+
+/// A possible error value when converting a `Version` from a string or a
+/// pair of version numbers.
+#[derive(Clone)]
+pub struct InvalidVersion {
+    _priv: (),
+}
+
+impl fmt::Debug for InvalidVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("InvalidVersion")
+            // skip _priv noise
+            .finish()
+    }
+}
+
+impl fmt::Display for InvalidVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("invalid HTTP version")
+    }
+}
+
+impl Error for InvalidVersion {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALL: [(Version, &str); 5] = [
+        (Version::HTTP_09, "HTTP/0.9"),
+        (Version::HTTP_10, "HTTP/1.0"),
+        (Version::HTTP_11, "HTTP/1.1"),
+        (Version::HTTP_2, "HTTP/2.0"),
+        (Version::HTTP_3, "HTTP/3.0"),
+    ];
+
+    #[test]
+    fn test_as_str_display_and_debug() {
+        for (version, s) in ALL {
+            assert_eq!(version.as_str(), s);
+            assert_eq!(version.to_string(), s);
+            assert_eq!(format!("{version:?}"), s);
+        }
+    }
+
+    #[test]
+    fn test_from_str_round_trips() {
+        for (version, s) in ALL {
+            assert_eq!(s.parse::<Version>().unwrap(), version);
+            assert_eq!(version.to_string().parse::<Version>().unwrap(), version);
+        }
+
+        for invalid in [
+            "",
+            "HTTP/2",
+            "http/1.1",
+            "HTTP/1.1 ",
+            "HTTP/10.0",
+            "HTTP/1.x",
+        ] {
+            assert!(invalid.parse::<Version>().is_err(), "{invalid:?}");
+        }
+    }
+
+    #[test]
+    fn test_numbers_round_trip() {
+        for (version, s) in ALL {
+            let numbers = (version.major(), version.minor());
+            assert_eq!(format!("HTTP/{}.{}", numbers.0, numbers.1), s);
+            assert_eq!(Version::try_from(numbers).unwrap(), version);
+        }
+
+        assert!(Version::HTTP_2.major() >= 2);
+        assert!(Version::HTTP_11.major() < 2);
+
+        for invalid in [(1, 2), (0, 0), (2, 1), (4, 0), (u8::MAX, u8::MAX)] {
+            assert!(Version::try_from(invalid).is_err(), "{invalid:?}");
+        }
+    }
+
+    #[test]
+    fn test_alpn() {
+        let tokens: [(Version, &[u8]); 4] = [
+            (Version::HTTP_10, b"http/1.0"),
+            (Version::HTTP_11, b"http/1.1"),
+            (Version::HTTP_2, b"h2"),
+            (Version::HTTP_3, b"h3"),
+        ];
+
+        for (version, token) in tokens {
+            assert_eq!(version.as_alpn(), Some(token));
+            assert_eq!(Version::from_alpn(token), Some(version));
+        }
+        assert_eq!(Version::HTTP_09.as_alpn(), None);
+
+        for garbage in [
+            &b""[..],
+            b"h2c",
+            b"H2",
+            b"HTTP/1.1",
+            b"http/0.9",
+            b"h2 ",
+            b"spdy/3",
+        ] {
+            assert_eq!(Version::from_alpn(garbage), None, "{garbage:?}");
+        }
+    }
+
+    #[test]
+    fn test_ordering_is_chronological() {
+        for (i, (a, _)) in ALL.iter().enumerate() {
+            for (j, (b, _)) in ALL.iter().enumerate() {
+                assert_eq!(a.cmp(b), i.cmp(&j), "{a:?} vs {b:?}");
+                assert_eq!(a.is_at_least(*b), i >= j, "{a:?} vs {b:?}");
+                assert_eq!(a.is_at_least(*b), a >= b, "{a:?} vs {b:?}");
+            }
+            assert_eq!(a.supports_multiplexing(), a.is_at_least(Version::HTTP_2));
+        }
+    }
+
+    #[test]
+    fn test_is_http2_and_http3() {
+        for (version, _) in ALL {
+            assert_eq!(version.is_http2(), version == Version::HTTP_2);
+            assert_eq!(version.is_http3(), version == Version::HTTP_3);
+        }
+        assert!("HTTP/2.0".parse::<Version>().unwrap().is_http2());
+        assert!(!Version::other(2, 1).unwrap().is_http2());
+    }
+
+    #[test]
+    fn test_other() {
+        use std::collections::hash_map::DefaultHasher;
+        use std::hash::{Hash, Hasher};
+
+        fn hash(version: Version) -> u64 {
+            let mut hasher = DefaultHasher::new();
+            version.hash(&mut hasher);
+            hasher.finish()
+        }
+
+        let http12: Version = "HTTP/1.2".parse().unwrap();
+        assert_eq!(http12, Version::other(1, 2).unwrap());
+        assert_eq!(http12.as_str(), "HTTP/1.2");
+        assert_eq!(http12.to_string(), "HTTP/1.2");
+        assert_eq!(format!("{http12:?}"), "HTTP/1.2");
+        assert_eq!((http12.major(), http12.minor()), (1, 2));
+        assert_eq!(hash(http12), hash(Version::other(1, 2).unwrap()));
+        assert_ne!(http12, Version::HTTP_11);
+        assert!(http12 > Version::HTTP_11 && http12 < Version::HTTP_2);
+        assert!(http12.is_at_least(Version::HTTP_11));
+        assert!(!http12.supports_multiplexing());
+        assert_eq!(http12.as_alpn(), None);
+        assert!(Version::try_from((1, 2)).is_err());
+
+        let http40 = Version::other(4, 0).unwrap();
+        assert!(http40 > Version::HTTP_3);
+        assert!(http40.supports_multiplexing());
+
+        for (version, s) in ALL {
+            let other = Version::other(version.major(), version.minor()).unwrap();
+            assert_eq!(other, version);
+            assert_eq!(hash(other), hash(version));
+            assert_eq!(other.as_str(), s);
+        }
+
+        for major in 0..10 {
+            for minor in 0..10 {
+                let version = Version::other(major, minor).unwrap();
+                let s = format!("HTTP/{major}.{minor}");
+                assert_eq!(version.as_str(), s);
+                assert_eq!(s.parse::<Version>().unwrap(), version);
+            }
+        }
+
+        assert!(Version::other(10, 0).is_err());
+        assert!(Version::other(1, 10).is_err());
     }
 }
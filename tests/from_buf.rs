@@ -0,0 +1,124 @@
+use std::collections::VecDeque;
+
+use bytes::{Buf, Bytes};
+use http::Uri;
+use http::header::{HeaderName, HeaderValue};
+
+/// A `Buf` made of separately allocated chunks, like a decoder's rope.
+struct Fragmented(VecDeque<Bytes>);
+
+impl Fragmented {
+    /// Splits `src` into chunks of at most `size` bytes.
+    fn new(src: &[u8], size: usize) -> Self {
+        Self(src.chunks(size).map(Bytes::copy_from_slice).collect())
+    }
+}
+
+impl Buf for Fragmented {
+    fn remaining(&self) -> usize {
+        self.0.iter().map(Bytes::len).sum()
+    }
+
+    fn chunk(&self) -> &[u8] {
+        self.0.front().map_or(&[], |b| &b[..])
+    }
+
+    fn advance(&mut self, mut cnt: usize) {
+        while cnt > 0 {
+            let front = self.0.front_mut().expect("advance past end");
+            let n = cnt.min(front.len());
+            front.advance(n);
+            cnt -= n;
+            if front.is_empty() {
+                self.0.pop_front();
+            }
+        }
+    }
+}
+
+/// Checks `f` against every chunking of `src`, with a trailing byte left over.
+fn check<T, E>(
+    src: &[u8],
+    f: impl Fn(&mut Fragmented, usize) -> Result<T, E>,
+    expected: &Result<T, E>,
+) where
+    T: PartialEq + std::fmt::Debug,
+    E: std::fmt::Debug,
+{
+    let mut with_trailer = src.to_vec();
+    with_trailer.push(b'!');
+
+    for size in 1..=with_trailer.len() {
+        let mut buf = Fragmented::new(&with_trailer, size);
+        let actual = f(&mut buf, src.len());
+        match (&actual, expected) {
+            (Ok(a), Ok(e)) => assert_eq!(a, e, "{src:?} in chunks of {size}"),
+            (Err(_), Err(_)) => {}
+            _ => panic!("{src:?} in chunks of {size}: got {actual:?}, expected {expected:?}"),
+        }
+        assert_eq!(buf.remaining(), 1, "{src:?} in chunks of {size}");
+        assert_eq!(buf.chunk(), b"!");
+    }
+}
+
+#[test]
+fn header_value_from_buf() {
+    let cases: &[&[u8]] = &[
+        b"",
+        b"text/html",
+        b"caf\xc3\xa9",
+        b"\xe2\x82\xac 10",
+        b"hello\xfa",
+        b"bad\nvalue",
+        b"\x7f",
+    ];
+
+    for &src in cases {
+        check(src, HeaderValue::from_buf, &HeaderValue::from_bytes(src));
+    }
+}
+
+#[test]
+fn header_name_from_buf() {
+    let cases: &[&[u8]] = &[
+        b"",
+        b"content-type",
+        b"Content-Type",
+        b"x-custom-header",
+        b"X-Custom-HEADER",
+        b"bad name",
+        b"caf\xc3\xa9",
+    ];
+
+    for &src in cases {
+        check(src, HeaderName::from_buf, &HeaderName::from_bytes(src));
+    }
+}
+
+#[test]
+fn uri_from_buf() {
+    let cases: &[&[u8]] = &[
+        b"",
+        b"/",
+        b"*",
+        b"example.com:8080",
+        b"/path?query=1",
+        b"https://user@example.com:443/a/b?c=d",
+        b"http://exa mple.com/",
+        b"/caf\xc3\xa9",
+    ];
+
+    for &src in cases {
+        check(src, Uri::from_buf, &Uri::try_from(src));
+    }
+}
+
+#[test]
+fn from_buf_reuses_contiguous_bytes() {
+    let mut buf = Bytes::from_static(b"text/html; charset=utf-8");
+    let ptr = buf.as_ptr();
+    let val = HeaderValue::from_buf(&mut buf, 9).unwrap();
+    assert_eq!(val, "text/html");
+    assert_eq!(val.as_bytes().as_ptr(), ptr);
+    assert_eq!(buf, "; charset=utf-8");
+}
@@ -669,3 +669,230 @@ fn ensure_miri_sharedreadonly_not_violated() {
 
     let _foo = &headers.iter().next();
 }
+
+#[test]
+fn debug_groups_multiple_values() {
+    let mut headers = HeaderMap::new();
+    headers.append(SET_COOKIE, "a=1".parse().unwrap());
+    headers.insert(CONTENT_TYPE, "text/html".parse().unwrap());
+    headers.append(SET_COOKIE, "b=2".parse().unwrap());
+
+    assert_eq!(
+        format!("{headers:?}"),
+        r#"{"set-cookie": ["a=1", "b=2"], "content-type": "text/html"}"#
+    );
+
+    assert_eq!(format!("{:?}", HeaderMap::<HeaderValue>::new()), "{}");
+}
+
+#[test]
+fn get_str_and_get_u64() {
+    let mut headers = HeaderMap::new();
+    headers.insert(CONTENT_LENGTH, HeaderValue::from_static("42"));
+    headers.append(CONTENT_LENGTH, HeaderValue::from_static("7"));
+    headers.insert("x-padded", HeaderValue::from_static(" 10 "));
+    headers.insert("x-signed", HeaderValue::from_static("+10"));
+    headers.insert("x-big", HeaderValue::from_static("18446744073709551616"));
+    headers.insert("x-bin", HeaderValue::from_bytes(b"\xff").unwrap());
+
+    assert_eq!(headers.get_str(&CONTENT_LENGTH), Some("42"));
+    assert_eq!(headers.get_u64(&CONTENT_LENGTH), Some(42));
+    assert_eq!(headers.get_u64(&"x-padded"), Some(10));
+    assert_eq!(headers.get_u64(&"x-signed"), None);
+    assert_eq!(headers.get_u64(&"x-big"), None);
+    assert_eq!(headers.get_str(&"x-bin"), None);
+    assert_eq!(headers.get_u64(&"x-bin"), None);
+    assert_eq!(headers.get_str(&"x-missing"), None);
+}
+
+#[test]
+fn get_int_strict_distinguishes_missing_from_malformed() {
+    let mut headers = HeaderMap::new();
+    headers.insert(AGE, HeaderValue::from_static("120"));
+    headers.insert(MAX_FORWARDS, HeaderValue::from_static("ten"));
+    headers.insert(
+        CONTENT_LENGTH,
+        HeaderValue::from_static("99999999999999999999"),
+    );
+
+    assert_eq!(headers.get_int(&AGE), Some(120));
+    assert_eq!(headers.get_int_strict(&AGE).unwrap().unwrap(), 120);
+
+    assert_eq!(headers.get_int(&MAX_FORWARDS), None);
+    assert!(headers.get_int_strict(&MAX_FORWARDS).unwrap().is_err());
+
+    assert_eq!(headers.get_int(&CONTENT_LENGTH), None);
+    let err = headers
+        .get_int_strict(&CONTENT_LENGTH)
+        .unwrap()
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "header value is not a valid unsigned integer"
+    );
+
+    assert!(headers.get_int_strict(&RETRY_AFTER).is_none());
+}
+
+#[test]
+fn has_multiple() {
+    let mut headers = HeaderMap::new();
+    assert!(!headers.has_multiple(&CONTENT_LENGTH));
+
+    headers.insert(CONTENT_LENGTH, HeaderValue::from_static("1"));
+    headers.insert(HOST, HeaderValue::from_static("example.com"));
+    assert!(!headers.has_multiple(&CONTENT_LENGTH));
+
+    headers.append(CONTENT_LENGTH, HeaderValue::from_static("2"));
+    assert!(headers.has_multiple(&CONTENT_LENGTH));
+    assert!(!headers.has_multiple(&HOST));
+
+    headers.insert(CONTENT_LENGTH, HeaderValue::from_static("3"));
+    assert!(!headers.has_multiple(&CONTENT_LENGTH));
+
+    headers.append(CONTENT_LENGTH, HeaderValue::from_static("4"));
+    headers.remove(&CONTENT_LENGTH);
+    assert!(!headers.has_multiple(&CONTENT_LENGTH));
+}
+
+#[test]
+fn value_count() {
+    let mut headers = HeaderMap::new();
+    assert_eq!(headers.value_count(&SET_COOKIE), 0);
+
+    headers.insert(SET_COOKIE, HeaderValue::from_static("a=1"));
+    headers.insert(HOST, HeaderValue::from_static("example.com"));
+    assert_eq!(headers.value_count(&SET_COOKIE), 1);
+
+    for i in 2..=5 {
+        headers.append(SET_COOKIE, HeaderValue::from_static("x=y"));
+        assert_eq!(headers.value_count(&SET_COOKIE), i);
+    }
+    assert_eq!(headers.value_count(&HOST), 1);
+    assert_eq!(headers.value_count(&"Set-Cookie"), 5);
+
+    headers.insert(SET_COOKIE, HeaderValue::from_static("z=1"));
+    assert_eq!(headers.value_count(&SET_COOKIE), 1);
+
+    headers.remove(&SET_COOKIE);
+    assert_eq!(headers.value_count(&SET_COOKIE), 0);
+}
+
+#[test]
+fn remove_sensitive() {
+    fn sensitive(s: &'static str) -> HeaderValue {
+        let mut value = HeaderValue::from_static(s);
+        value.set_sensitive(true);
+        value
+    }
+
+    let mut headers = HeaderMap::new();
+    headers.insert(HOST, HeaderValue::from_static("example.com"));
+    headers.insert(AUTHORIZATION, sensitive("Basic dXNlcjpwYXNz"));
+    // The first value of a key being sensitive must not lose the rest.
+    headers.append(SET_COOKIE, sensitive("session=1"));
+    headers.append(SET_COOKIE, HeaderValue::from_static("theme=dark"));
+    headers.append(SET_COOKIE, sensitive("token=2"));
+    headers.append(SET_COOKIE, HeaderValue::from_static("lang=en"));
+    headers.append("x-api-key", sensitive("k1"));
+    headers.append("x-api-key", sensitive("k2"));
+
+    let cloned = headers.clone_non_sensitive();
+    assert_eq!(headers.len(), 8);
+    let removed = headers.remove_sensitive();
+
+    for map in [&cloned, &removed] {
+        assert_eq!(map.len(), 3);
+        assert_eq!(map.keys_len(), 2);
+        assert_eq!(map[HOST], "example.com");
+        assert!(!map.contains_key(&AUTHORIZATION));
+        assert!(!map.contains_key(&"x-api-key"));
+        let cookies: Vec<_> = map.get_all(&SET_COOKIE).iter().collect();
+        assert_eq!(cookies, ["theme=dark", "lang=en"]);
+        assert!(map.values().all(|value| !value.is_sensitive()));
+    }
+    assert_eq!(cloned, removed);
+
+    assert!(HeaderMap::new().remove_sensitive().is_empty());
+}
+
+#[test]
+fn apply_defaults() {
+    let mut defaults = HeaderMap::new();
+    defaults.insert(
+        CONTENT_TYPE,
+        HeaderValue::from_static("application/octet-stream"),
+    );
+    defaults.append(CACHE_CONTROL, HeaderValue::from_static("no-store"));
+    defaults.append(CACHE_CONTROL, HeaderValue::from_static("private"));
+    defaults.append(VARY, HeaderValue::from_static("accept"));
+
+    let mut headers = HeaderMap::new();
+    headers.append(VARY, HeaderValue::from_static("origin"));
+    headers.append(VARY, HeaderValue::from_static("cookie"));
+    headers.insert(HOST, HeaderValue::from_static("example.com"));
+    headers.apply_defaults(&defaults);
+
+    assert_eq!(headers.keys_len(), 4);
+    assert_eq!(headers[CONTENT_TYPE], "application/octet-stream");
+    let cache: Vec<_> = headers.get_all(&CACHE_CONTROL).iter().collect();
+    assert_eq!(cache, ["no-store", "private"]);
+    let vary: Vec<_> = headers.get_all(&VARY).iter().collect();
+    assert_eq!(vary, ["origin", "cookie"]);
+    assert_eq!(headers[HOST], "example.com");
+
+    // Applying again changes nothing.
+    let before = headers.clone();
+    headers.apply_defaults(&defaults);
+    assert_eq!(headers, before);
+
+    let mut empty = HeaderMap::new();
+    empty.apply_defaults(&defaults);
+    assert_eq!(empty, defaults);
+    headers.apply_defaults(&HeaderMap::new());
+    assert_eq!(headers, before);
+}
+
+#[test]
+fn clear_keeps_capacity_for_reuse() {
+    let mut headers = HeaderMap::new();
+    for i in 0..50 {
+        let name: HeaderName = format!("x-header-{i}").parse().unwrap();
+        headers.insert(name.clone(), "a".parse().unwrap());
+        headers.append(name, "b".parse().unwrap());
+    }
+    let capacity = headers.capacity();
+
+    headers.clear();
+    assert!(headers.is_empty());
+    assert_eq!(headers.len(), 0);
+    assert_eq!(headers.keys_len(), 0);
+    assert_eq!(headers.capacity(), capacity);
+    assert!(headers.get(&"x-header-0").is_none());
+    assert_eq!(headers.iter().count(), 0);
+
+    for i in 0..50 {
+        let name: HeaderName = format!("x-header-{i}").parse().unwrap();
+        assert!(headers.insert(name, "c".parse().unwrap()).is_none());
+    }
+    assert_eq!(headers.len(), 50);
+    assert_eq!(headers.capacity(), capacity);
+    assert_eq!(headers["x-header-49"], "c");
+    assert_eq!(headers.get_all(&"x-header-0").iter().count(), 1);
+}
+
+#[test]
+fn get_or_empty() {
+    let mut headers = HeaderMap::new();
+    assert_eq!(headers.get_or_empty(&HOST), "");
+    assert_eq!(headers.get_or_empty(&"host"), "");
+
+    headers.insert(HOST, HeaderValue::from_static("example.com"));
+    headers.append(HOST, HeaderValue::from_static("example.org"));
+    assert_eq!(headers.get_or_empty(&HOST), "example.com");
+    assert_eq!(headers.get_or_empty(&"Host"), "example.com");
+
+    let missing = headers.get_or_empty(&ACCEPT);
+    assert!(missing.is_empty());
+    assert!(!missing.is_sensitive());
+}
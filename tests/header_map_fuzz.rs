@@ -191,7 +191,7 @@ impl AltMap {
         if existing {
             // Existing header
             self.find_random_name(rng)
-                .map_or_else(|| gen_header_name(rng), |name| name)
+                .unwrap_or_else(|| gen_header_name(rng))
         } else {
             gen_header_name(rng)
         }
@@ -0,0 +1,166 @@
+#![cfg(feature = "serde")]
+
+use http::header::*;
+use http::*;
+
+fn to_json<F>(serialize: F) -> String
+where
+    F: FnOnce(&mut serde_json::Serializer<&mut Vec<u8>>) -> serde_json::Result<()>,
+{
+    let mut json = Vec::new();
+    serialize(&mut serde_json::Serializer::new(&mut json)).unwrap();
+    String::from_utf8(json).unwrap()
+}
+
+#[test]
+fn uri_round_trip() {
+    let uri = Uri::from_static("http://example.com:8080/path?q=1");
+    let json = to_json(|ser| http::serde::uri::serialize(&uri, ser));
+    assert_eq!(json, r#""http://example.com:8080/path?q=1""#);
+
+    let mut de = serde_json::Deserializer::from_str(&json);
+    assert_eq!(http::serde::uri::deserialize(&mut de).unwrap(), uri);
+
+    let mut de = serde_json::Deserializer::from_str(r#""not a uri""#);
+    assert!(http::serde::uri::deserialize(&mut de).is_err());
+}
+
+#[test]
+fn method_round_trip() {
+    let method = Method::from_bytes(b"PURGE").unwrap();
+    let json = to_json(|ser| http::serde::method::serialize(&method, ser));
+    assert_eq!(json, r#""PURGE""#);
+
+    let mut de = serde_json::Deserializer::from_str(&json);
+    assert_eq!(http::serde::method::deserialize(&mut de).unwrap(), method);
+
+    let mut de = serde_json::Deserializer::from_str(r#""BAD METHOD""#);
+    assert!(http::serde::method::deserialize(&mut de).is_err());
+}
+
+#[test]
+fn status_code_round_trip() {
+    let json = to_json(|ser| http::serde::status_code::serialize(&StatusCode::IM_A_TEAPOT, ser));
+    assert_eq!(json, "418");
+
+    let mut de = serde_json::Deserializer::from_str(&json);
+    assert_eq!(
+        http::serde::status_code::deserialize(&mut de).unwrap(),
+        StatusCode::IM_A_TEAPOT
+    );
+
+    for invalid in ["99", "1000", "-200", "\"200\""] {
+        let mut de = serde_json::Deserializer::from_str(invalid);
+        assert!(
+            http::serde::status_code::deserialize(&mut de).is_err(),
+            "{invalid}"
+        );
+    }
+}
+
+#[test]
+fn header_map_round_trip() {
+    let mut headers = HeaderMap::new();
+    headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+    headers.append(SET_COOKIE, HeaderValue::from_static("a=1"));
+    headers.append(SET_COOKIE, HeaderValue::from_static("b=2"));
+    headers.insert("x-opaque", HeaderValue::from_bytes(b"caf\xe9").unwrap());
+
+    let json = to_json(|ser| http::serde::header_map::serialize(&headers, ser));
+    assert_eq!(
+        json,
+        r#"{"content-type":"application/json","set-cookie":["a=1","b=2"],"x-opaque":[99,97,102,233]}"#
+    );
+
+    let mut de = serde_json::Deserializer::from_str(
+        r#"{"Content-Type":"application/json","set-cookie":["a=1","b=2"],"x-opaque":"caf"}"#,
+    );
+    let parsed = http::serde::header_map::deserialize(&mut de).unwrap();
+    assert_eq!(parsed[CONTENT_TYPE], "application/json");
+    assert_eq!(parsed.get_all(&SET_COOKIE).iter().count(), 2);
+    assert_eq!(parsed["x-opaque"], "caf");
+
+    for invalid in [
+        r#"{"bad name":"x"}"#,
+        r#"{"x":"bad\nvalue"}"#,
+        r#"{"x":[["nested"]]}"#,
+        r#"["not a map"]"#,
+    ] {
+        let mut de = serde_json::Deserializer::from_str(invalid);
+        assert!(
+            http::serde::header_map::deserialize(&mut de).is_err(),
+            "{invalid}"
+        );
+    }
+}
+
+#[test]
+fn header_map_seq_round_trip() {
+    let mut headers = HeaderMap::new();
+    headers.insert(CONTENT_TYPE, HeaderValue::from_static("text/html"));
+    headers.append(SET_COOKIE, HeaderValue::from_static("a=1"));
+    headers.append(SET_COOKIE, HeaderValue::from_static("b=2"));
+    headers.insert("x-opaque", HeaderValue::from_bytes(b"\xff").unwrap());
+
+    let json = to_json(|ser| http::serde::header_map_seq::serialize(&headers, ser));
+    assert_eq!(
+        json,
+        r#"{"content-type":["text/html"],"set-cookie":["a=1","b=2"],"x-opaque":[[255]]}"#
+    );
+
+    let json = to_json(|ser| http::serde::header_map_seq::serialize(&HeaderMap::new(), ser));
+    assert_eq!(json, "{}");
+
+    let mut de = serde_json::Deserializer::from_str(r#"{"a":["1"],"b":"2"}"#);
+    let parsed = http::serde::header_map_seq::deserialize(&mut de).unwrap();
+    assert_eq!(parsed["a"], "1");
+    assert_eq!(parsed["b"], "2");
+}
+
+#[test]
+fn trait_impls_round_trip() {
+    fn round_trip<T>(value: &T, json: &str)
+    where
+        T: ::serde::Serialize + ::serde::de::DeserializeOwned + PartialEq + std::fmt::Debug,
+    {
+        assert_eq!(serde_json::to_string(value).unwrap(), json);
+        assert_eq!(&serde_json::from_str::<T>(json).unwrap(), value);
+    }
+
+    round_trip(&StatusCode::CREATED, "201");
+    round_trip(&Method::OPTIONS, r#""OPTIONS""#);
+    round_trip(&Method::from_bytes(b"PURGE").unwrap(), r#""PURGE""#);
+    round_trip(&Version::HTTP_11, r#""HTTP/1.1""#);
+    round_trip(&Version::HTTP_2, r#""HTTP/2.0""#);
+    round_trip(
+        &Uri::from_static("https://example.com/a?b"),
+        r#""https://example.com/a?b""#,
+    );
+    round_trip(
+        &uri::Authority::from_static("user@example.com:8080"),
+        r#""user@example.com:8080""#,
+    );
+    round_trip(&uri::Scheme::HTTPS, r#""https""#);
+
+    let uri = Uri::from_static("/a");
+    assert_eq!(
+        serde_json::to_string(&uri).unwrap(),
+        to_json(|ser| http::serde::uri::serialize(&uri, ser))
+    );
+}
+
+#[test]
+fn trait_impls_reject_invalid_input() {
+    fn error<T: ::serde::de::DeserializeOwned + std::fmt::Debug>(json: &str) -> String {
+        serde_json::from_str::<T>(json).unwrap_err().to_string()
+    }
+
+    assert!(error::<StatusCode>("1000").starts_with("invalid value: integer `1000`"));
+    assert!(error::<StatusCode>(r#""200""#).starts_with("invalid type: string"));
+    assert!(error::<Method>(r#""BAD METHOD""#).starts_with("invalid HTTP method"));
+    assert!(error::<Version>(r#""HTTP/10.0""#).starts_with("invalid HTTP version"));
+    assert!(error::<Uri>(r#""not a uri""#).starts_with("invalid uri character"));
+    assert!(error::<uri::Authority>(r#""a b""#).starts_with("invalid uri character"));
+    assert!(error::<uri::Scheme>(r#""ht tp""#).starts_with("invalid"));
+    assert!(error::<Uri>("42").starts_with("invalid type: integer `42`, expected a URI"));
+}
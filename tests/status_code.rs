@@ -76,7 +76,144 @@ fn is_server_error() {
     assert!(!status_code(600).is_server_error());
 }
 
+#[test]
+fn is_error() {
+    assert!(status_code(400).is_error());
+    assert!(status_code(499).is_error());
+    assert!(status_code(500).is_error());
+    assert!(status_code(599).is_error());
+
+    assert!(!status_code(399).is_error());
+    assert!(!status_code(600).is_error());
+
+    const { assert!(StatusCode::BAD_GATEWAY.is_error()) };
+}
+
 /// Helper method for readability
 fn status_code(status_code: u16) -> StatusCode {
     StatusCode::from_u16(status_code).unwrap()
 }
+
+#[cfg(feature = "error")]
+#[test]
+fn usable_as_boxed_error() {
+    fn handler() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        Err(StatusCode::NOT_FOUND)?
+    }
+
+    let err = handler().unwrap_err();
+    assert_eq!(err.to_string(), "404 Not Found");
+    assert_eq!(
+        err.downcast_ref::<StatusCode>(),
+        Some(&StatusCode::NOT_FOUND)
+    );
+}
+
+#[test]
+fn from_informational_offset() {
+    for offset in 0..10u8 {
+        let status = StatusCode::from_informational_offset(offset).unwrap();
+        assert_eq!(status.as_u16(), 100 + u16::from(offset));
+        assert!(status.is_informational());
+    }
+
+    assert_eq!(
+        StatusCode::from_informational_offset(1),
+        Some(StatusCode::SWITCHING_PROTOCOLS)
+    );
+    assert_eq!(StatusCode::from_informational_offset(10), None);
+    assert_eq!(StatusCode::from_informational_offset(u8::MAX), None);
+}
+
+#[test]
+#[allow(deprecated)]
+fn unprocessable_content_alias() {
+    assert_eq!(
+        StatusCode::UNPROCESSABLE_CONTENT,
+        StatusCode::UNPROCESSABLE_ENTITY
+    );
+    assert_eq!(StatusCode::UNPROCESSABLE_CONTENT.as_u16(), 422);
+    assert_eq!(
+        StatusCode::UNPROCESSABLE_CONTENT.canonical_reason(),
+        Some("Unprocessable Entity")
+    );
+}
+
+#[test]
+fn class() {
+    use http::status::StatusClass;
+
+    let cases = [
+        (100, StatusClass::Informational),
+        (199, StatusClass::Informational),
+        (200, StatusClass::Success),
+        (299, StatusClass::Success),
+        (300, StatusClass::Redirection),
+        (399, StatusClass::Redirection),
+        (400, StatusClass::ClientError),
+        (499, StatusClass::ClientError),
+        (500, StatusClass::ServerError),
+        (599, StatusClass::ServerError),
+        (600, StatusClass::Unclassified),
+        (999, StatusClass::Unclassified),
+    ];
+
+    for (code, class) in cases {
+        let status = StatusCode::from_u16(code).unwrap();
+        assert_eq!(status.class(), class, "{code}");
+        assert!(class.range().contains(&code), "{code}");
+    }
+
+    for code in 100..1000 {
+        let status = StatusCode::from_u16(code).unwrap();
+        assert!(status.class().range().contains(&code), "{code}");
+        assert_eq!(
+            status.class() == StatusClass::Success,
+            status.is_success(),
+            "{code}"
+        );
+    }
+}
+
+#[test]
+fn early_hints() {
+    assert_eq!(StatusCode::EARLY_HINTS, StatusCode::from_u16(103).unwrap());
+    assert_eq!(
+        StatusCode::EARLY_HINTS.canonical_reason(),
+        Some("Early Hints")
+    );
+    assert_eq!(
+        StatusCode::from_informational_offset(3),
+        Some(StatusCode::EARLY_HINTS)
+    );
+    assert!(StatusCode::EARLY_HINTS.is_informational());
+}
+
+#[test]
+fn status_line_bytes() {
+    let mut registered = 0;
+
+    for code in 100..1000 {
+        let status = StatusCode::from_u16(code).unwrap();
+
+        assert_eq!(
+            status.canonical_reason_bytes(),
+            status.canonical_reason().map(str::as_bytes),
+            "{code}"
+        );
+
+        match status.status_line_bytes() {
+            Some(line) => {
+                assert_eq!(line, format!("{status}").as_bytes(), "{code}");
+                registered += 1;
+            }
+            None => assert!(status.canonical_reason().is_none(), "{code}"),
+        }
+    }
+
+    assert_eq!(registered, 62);
+    assert_eq!(
+        StatusCode::UNPROCESSABLE_CONTENT.status_line_bytes(),
+        Some(&b"422 Unprocessable Entity"[..])
+    );
+}